@@ -1,19 +1,54 @@
 use crate::error::Error;
-use crate::input_parser::{self, Input};
-use crate::utils;
+use crate::input_parser::{self, arg, arg_names, Input};
+use crate::storage::{Scope, VotingStorage};
+use crate::utils::{self, Role};
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
 use casperlabs_contract::args_parser::ArgsParser;
 use casperlabs_contract::contract_api::runtime;
-use logic::Voting;
+use casperlabs_types::{
+    account::PublicKey,
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped,
+};
+use logic::{
+    Multisig, MultisigAction, MultisigSerialized, ParticipantSerialized, ProjectId,
+    ProjectSerialized, ResultsSerialized, Voting, VotingError,
+};
 
 const CONTRACT_NAME: &str = "voting_contract";
 const INDIRECT_NAME: &str = "voting_indirect";
-const VOTING_KEY: &str = "voting_data";
+const RESULTS_KEY: &str = "voting_results";
+const PAUSE_KEY: &str = "is_paused";
+const MULTISIG_KEY: &str = "multisig";
+
+// Session code can't return values, so `voting_indirect` saves the answers
+// of the queries in the caller's account under these keys.
+const PROJECT_ANSWER_KEY: &str = "query_project";
+const PARTICIPANT_ANSWER_KEY: &str = "query_participant";
+const REMAINING_POWER_ANSWER_KEY: &str = "query_remaining_power";
+const RESULTS_ANSWER_KEY: &str = "query_results";
 
 #[no_mangle]
 pub extern "C" fn call() {
     match input_parser::from_args() {
-        Input::Deploy(start_at, end_at) => {
-            let init_args = (input_parser::DEPLOY, start_at, end_at);
+        Input::Deploy(start_at, end_at, vote_mode, reveal_end_at) => {
+            let init_args = (
+                input_parser::DEPLOY,
+                arg_names(&[
+                    arg::START_AT,
+                    arg::END_AT,
+                    arg::VOTE_MODE,
+                    arg::APPROVAL_LIMIT,
+                    arg::REVEAL_END_AT,
+                ]),
+                start_at,
+                end_at,
+                input_parser::vote_mode_name(vote_mode),
+                input_parser::approval_limit(vote_mode),
+                reveal_end_at.unwrap_or(0),
+            );
             utils::deploy_code_and_init(CONTRACT_NAME, CONTRACT_NAME, init_args);
             utils::deploy_code(INDIRECT_NAME, INDIRECT_NAME);
         }
@@ -26,27 +61,170 @@ fn voting_indirect() {
     match input_parser::from_args() {
         Input::AddOrUpdateParticipant(public_key, voting_power) => call_voting_contract((
             input_parser::ADD_OR_UPDATE_PARTICIPANT,
+            arg_names(&[arg::PARTICIPANT, arg::VOTING_POWER]),
             public_key,
             voting_power,
         )),
-        Input::RemoveParticipant(public_key) => {
-            call_voting_contract((input_parser::REMOVE_PARTICIPANT, public_key))
-        }
+        Input::AddOrUpdateParticipants(participants) => call_voting_contract((
+            input_parser::ADD_OR_UPDATE_PARTICIPANTS,
+            arg_names(&[arg::PARTICIPANTS]),
+            participants,
+        )),
+        Input::RemoveParticipant(public_key) => call_voting_contract((
+            input_parser::REMOVE_PARTICIPANT,
+            arg_names(&[arg::PARTICIPANT]),
+            public_key,
+        )),
+        Input::AddTeamMember(public_key, project_id) => call_voting_contract((
+            input_parser::ADD_TEAM_MEMBER,
+            arg_names(&[arg::PARTICIPANT, arg::PROJECT_ID]),
+            public_key,
+            project_id.0,
+        )),
+        Input::RemoveTeamMember(public_key) => call_voting_contract((
+            input_parser::REMOVE_TEAM_MEMBER,
+            arg_names(&[arg::PARTICIPANT]),
+            public_key,
+        )),
         Input::AddOrUpdateProject(project_id, project) => call_voting_contract((
             input_parser::ADD_OR_UPDATE_PROJECT,
+            arg_names(&[arg::PROJECT_ID, arg::NAME, arg::TEAM, arg::ATTRIBUTES]),
             project_id.0,
             project.name,
             project.team_name,
-            project.video_link,
-            project.github_link,
-            project.google_drive_link,
+            project.attributes,
         )),
-        Input::RemoveProject(project_id) => {
-            call_voting_contract((input_parser::REMOVE_PROJECT, project_id.0))
-        }
-        Input::CastVote(project_id, vote) => {
-            call_voting_contract((input_parser::CAST_VOTE, project_id.0, vote))
-        }
+        Input::AddOrUpdateProjects(projects) => call_voting_contract((
+            input_parser::ADD_OR_UPDATE_PROJECTS,
+            arg_names(&[arg::PROJECTS]),
+            Voting::serialize_projects(&projects),
+        )),
+        Input::RemoveProject(project_id) => call_voting_contract((
+            input_parser::REMOVE_PROJECT,
+            arg_names(&[arg::PROJECT_ID]),
+            project_id.0,
+        )),
+        Input::SetProjectAttribute(project_id, key, value) => call_voting_contract((
+            input_parser::SET_PROJECT_ATTRIBUTE,
+            arg_names(&[arg::PROJECT_ID, arg::KEY, arg::VALUE]),
+            project_id.0,
+            key,
+            value,
+        )),
+        Input::DeleteProjectAttribute(project_id, key) => call_voting_contract((
+            input_parser::DELETE_PROJECT_ATTRIBUTE,
+            arg_names(&[arg::PROJECT_ID, arg::KEY]),
+            project_id.0,
+            key,
+        )),
+        Input::CastVote(project_id, vote) => call_voting_contract((
+            input_parser::CAST_VOTE,
+            arg_names(&[arg::PROJECT_ID, arg::VOTING_POWER]),
+            project_id.0,
+            vote,
+        )),
+        Input::RetractVote(project_id) => call_voting_contract((
+            input_parser::RETRACT_VOTE,
+            arg_names(&[arg::PROJECT_ID]),
+            project_id.0,
+        )),
+        Input::ChangeVote(from_project_id, to_project_id) => call_voting_contract((
+            input_parser::CHANGE_VOTE,
+            arg_names(&[arg::FROM_PROJECT_ID, arg::TO_PROJECT_ID]),
+            from_project_id.0,
+            to_project_id.0,
+        )),
+        Input::CastRankedVote(ranking) => call_voting_contract((
+            input_parser::CAST_RANKED_VOTE,
+            arg_names(&[arg::RANKING]),
+            serialize_ranking(ranking),
+        )),
+        Input::CommitVote(commitment) => call_voting_contract((
+            input_parser::COMMIT_VOTE,
+            arg_names(&[arg::COMMITMENT]),
+            commitment,
+        )),
+        Input::RevealVote(project_id, vote, salt) => call_voting_contract((
+            input_parser::REVEAL_VOTE,
+            arg_names(&[arg::PROJECT_ID, arg::VOTING_POWER, arg::SALT]),
+            project_id.0,
+            vote,
+            salt,
+        )),
+        Input::DelegateVotingPower(delegate, voting_power) => call_voting_contract((
+            input_parser::DELEGATE_VOTING_POWER,
+            arg_names(&[arg::DELEGATE, arg::VOTING_POWER]),
+            delegate,
+            voting_power,
+        )),
+        Input::RevokeDelegation(delegate) => call_voting_contract((
+            input_parser::REVOKE_DELEGATION,
+            arg_names(&[arg::DELEGATE]),
+            delegate,
+        )),
+        Input::PublishResults => call_voting_contract((input_parser::PUBLISH_RESULTS,)),
+        Input::Finalize => call_voting_contract((input_parser::FINALIZE,)),
+        Input::SetVotingWindow(start_at, end_at) => call_voting_contract((
+            input_parser::SET_VOTING_WINDOW,
+            arg_names(&[arg::START_AT, arg::END_AT]),
+            start_at,
+            end_at,
+        )),
+        Input::Pause => call_voting_contract((input_parser::PAUSE,)),
+        Input::Unpause => call_voting_contract((input_parser::UNPAUSE,)),
+        Input::ProposeAdmin(public_key) => call_voting_contract((
+            input_parser::PROPOSE_ADMIN,
+            arg_names(&[arg::ADMIN]),
+            public_key,
+        )),
+        Input::AcceptAdmin => call_voting_contract((input_parser::ACCEPT_ADMIN,)),
+        Input::GrantRole(account, role) => call_voting_contract((
+            input_parser::GRANT_ROLE,
+            arg_names(&[arg::ACCOUNT, arg::ROLE]),
+            account,
+            input_parser::role_name(role),
+        )),
+        Input::SetMultisig(approvers, threshold, timeout) => call_voting_contract((
+            input_parser::SET_MULTISIG,
+            arg_names(&[arg::APPROVERS, arg::THRESHOLD, arg::TIMEOUT]),
+            approvers,
+            threshold,
+            timeout,
+        )),
+        Input::RevokeRole(account, role) => call_voting_contract((
+            input_parser::REVOKE_ROLE,
+            arg_names(&[arg::ACCOUNT, arg::ROLE]),
+            account,
+            input_parser::role_name(role),
+        )),
+        Input::GetProject(project_id) => query_voting_contract::<ProjectSerialized>(
+            PROJECT_ANSWER_KEY,
+            (
+                input_parser::GET_PROJECT,
+                arg_names(&[arg::PROJECT_ID]),
+                project_id.0,
+            ),
+        ),
+        Input::GetParticipant(public_key) => query_voting_contract::<ParticipantSerialized>(
+            PARTICIPANT_ANSWER_KEY,
+            (
+                input_parser::GET_PARTICIPANT,
+                arg_names(&[arg::PARTICIPANT]),
+                public_key,
+            ),
+        ),
+        Input::GetRemainingPower(public_key) => query_voting_contract::<u64>(
+            REMAINING_POWER_ANSWER_KEY,
+            (
+                input_parser::GET_REMAINING_POWER,
+                arg_names(&[arg::PARTICIPANT]),
+                public_key,
+            ),
+        ),
+        Input::GetResults => query_voting_contract::<ResultsSerialized>(
+            RESULTS_ANSWER_KEY,
+            (input_parser::GET_RESULTS,),
+        ),
         _ => runtime::revert(Error::UnknownIndirectCommand),
     }
 }
@@ -56,44 +234,109 @@ fn voting_contract() {
     utils::init_or_handle(init_voting, handle_voting);
 }
 
-fn init_voting() -> Result<(), Error> {
+fn init_voting() -> Result<(), ApiError> {
     match input_parser::from_args() {
-        Input::Deploy(start_at, end_at) => {
+        Input::Deploy(start_at, end_at, vote_mode, reveal_end_at) => {
             utils::set_admin_account(runtime::get_caller());
-            Voting::new(start_at, end_at)
-                .map(save_voting)
-                .map_err(Error::from)
+            let mut voting = Voting::with_vote_mode(start_at, end_at, vote_mode)?;
+            if let Some(reveal_end_at) = reveal_end_at {
+                voting.enable_secret_ballot(reveal_end_at)?;
+            }
+            utils::set_key(PAUSE_KEY, false);
+            utils::set_key(MULTISIG_KEY, None::<MultisigSerialized>);
+            VotingStorage::new().save(voting)
         }
-        _ => Err(Error::UnknownInitCommand),
+        _ => Err(Error::UnknownInitCommand.into()),
     }
 }
 
-fn handle_voting() -> Result<(), Error> {
-    let mut voting = read_voting();
-    match input_parser::from_args() {
+fn handle_voting() -> Result<(), ApiError> {
+    let input = input_parser::from_args();
+    if is_participant_call(&input) && is_paused() {
+        return Err(Error::VotingPaused.into());
+    }
+    let (storage, mut voting) = VotingStorage::load(scope(&input))?;
+    match input {
         Input::AddOrUpdateParticipant(public_key, voting_power) => {
-            utils::assert_admin();
-            voting.add_or_update_participant(public_key, voting_power);
-            save_voting(voting);
-            Ok(())
+            utils::assert_role(Role::ParticipantManager);
+            voting
+                .add_or_update_participant(public_key, voting_power)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::AddOrUpdateParticipants(participants) => {
+            utils::assert_role(Role::ParticipantManager);
+            voting
+                .add_or_update_participants(participants)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
         }
         Input::RemoveParticipant(public_key) => {
-            utils::assert_admin();
-            voting.remove_participant_if_exists(&public_key);
-            save_voting(voting);
-            Ok(())
+            let action = MultisigAction::RemoveParticipant(public_key);
+            if !approve(action, Role::ParticipantManager)? {
+                return Ok(());
+            }
+            voting
+                .remove_participant_if_exists(&public_key)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::AddTeamMember(public_key, project_id) => {
+            utils::assert_role(Role::ParticipantManager);
+            voting
+                .add_team_member(public_key, project_id)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::RemoveTeamMember(public_key) => {
+            utils::assert_role(Role::ParticipantManager);
+            voting
+                .remove_team_member(public_key)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
         }
         Input::AddOrUpdateProject(project_id, project) => {
-            utils::assert_admin();
-            voting.add_or_update_project(project_id, project);
-            save_voting(voting);
-            Ok(())
+            utils::assert_role(Role::ProjectManager);
+            assert_paused_if_voting_open(&voting)?;
+            voting
+                .add_or_update_project(project_id, project)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::AddOrUpdateProjects(projects) => {
+            utils::assert_role(Role::ProjectManager);
+            assert_paused_if_voting_open(&voting)?;
+            voting
+                .add_or_update_projects(projects)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
         }
         Input::RemoveProject(project_id) => {
-            utils::assert_admin();
-            voting.remove_project_if_exists_and_cancel_votes(project_id);
-            save_voting(voting);
-            Ok(())
+            let action = MultisigAction::RemoveProject(project_id);
+            if !approve(action, Role::ProjectManager)? {
+                return Ok(());
+            }
+            assert_paused_if_voting_open(&voting)?;
+            voting
+                .remove_project_if_exists_and_cancel_votes(project_id)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::SetProjectAttribute(project_id, key, value) => {
+            utils::assert_role(Role::ProjectManager);
+            assert_paused_if_voting_open(&voting)?;
+            voting
+                .set_project_attribute(project_id, key, value)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::DeleteProjectAttribute(project_id, key) => {
+            utils::assert_role(Role::ProjectManager);
+            assert_paused_if_voting_open(&voting)?;
+            voting
+                .delete_project_attribute(project_id, &key)
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
         }
         Input::CastVote(project_id, vote) => voting
             .cast_vote(
@@ -102,22 +345,262 @@ fn handle_voting() -> Result<(), Error> {
                 vote,
                 runtime::get_blocktime().into(),
             )
-            .map(|_| save_voting(voting))
-            .map_err(Error::from),
-        _ => Err(Error::UnknownContractCommand),
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::RetractVote(project_id) => voting
+            .retract_vote(
+                runtime::get_caller(),
+                project_id,
+                runtime::get_blocktime().into(),
+            )
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::ChangeVote(from_project_id, to_project_id) => voting
+            .change_vote(
+                runtime::get_caller(),
+                from_project_id,
+                to_project_id,
+                runtime::get_blocktime().into(),
+            )
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::CastRankedVote(ranking) => voting
+            .cast_ranked_vote(
+                runtime::get_caller(),
+                ranking,
+                runtime::get_blocktime().into(),
+            )
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::CommitVote(commitment) => voting
+            .commit_vote(
+                runtime::get_caller(),
+                commitment,
+                runtime::get_blocktime().into(),
+            )
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::RevealVote(project_id, vote, salt) => voting
+            .reveal_vote(
+                runtime::get_caller(),
+                project_id,
+                vote,
+                salt,
+                runtime::get_blocktime().into(),
+            )
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::DelegateVotingPower(delegate, voting_power) => voting
+            .delegate_voting_power(
+                runtime::get_caller(),
+                delegate,
+                voting_power,
+                runtime::get_blocktime().into(),
+            )
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::RevokeDelegation(delegate) => voting
+            .revoke_delegation(
+                runtime::get_caller(),
+                delegate,
+                runtime::get_blocktime().into(),
+            )
+            .map_err(ApiError::from)
+            .and_then(|_| storage.save(voting)),
+        Input::PublishResults => voting
+            .results(runtime::get_blocktime().into())
+            .map(save_results)
+            .map_err(ApiError::from),
+        Input::Finalize => {
+            utils::assert_role(Role::Owner);
+            voting
+                .finalize(runtime::get_blocktime().into())
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::SetVotingWindow(start_at, end_at) => {
+            utils::assert_role(Role::Owner);
+            voting
+                .set_voting_window(start_at, end_at, runtime::get_blocktime().into())
+                .map_err(ApiError::from)
+                .and_then(|_| storage.save(voting))
+        }
+        Input::Pause => {
+            utils::assert_role(Role::Owner);
+            pause()
+        }
+        Input::Unpause => {
+            utils::assert_role(Role::Owner);
+            unpause()
+        }
+        Input::ProposeAdmin(public_key) => {
+            utils::assert_role(Role::Owner);
+            utils::propose_admin_account(public_key);
+            Ok(())
+        }
+        Input::AcceptAdmin => utils::accept_admin_account().map_err(ApiError::from),
+        Input::GrantRole(account, role) => {
+            utils::assert_role(Role::Owner);
+            utils::grant_role(account, role.into());
+            Ok(())
+        }
+        Input::RevokeRole(account, role) => {
+            utils::assert_role(Role::Owner);
+            utils::revoke_role(account, role.into());
+            Ok(())
+        }
+        Input::SetMultisig(approvers, threshold, timeout) => {
+            set_multisig(approvers, threshold, timeout)
+        }
+        Input::GetProject(project_id) => match voting.projects.get(&project_id) {
+            Some(project) => utils::ret(Voting::serialize_project(project)),
+            None => Err(VotingError::ProjectDoesNotExists { project_id }.into()),
+        },
+        Input::GetParticipant(public_key) => match voting.participants.get(&public_key) {
+            Some(participant) => utils::ret(Voting::serialize_participant(participant)),
+            None => Err(VotingError::NotAParticipant.into()),
+        },
+        Input::GetRemainingPower(public_key) => {
+            let remaining_voting_power = voting.remaining_voting_power(&public_key)?;
+            utils::ret(remaining_voting_power)
+        }
+        Input::GetResults => {
+            let results = voting.results(runtime::get_blocktime().into())?;
+            utils::ret(Voting::serialize_results(&results))
+        }
+        _ => Err(Error::UnknownContractCommand.into()),
+    }
+}
+
+// Reveals are not blocked: the reveal phase can't be moved, so committed
+// votes would be lost if it ended during the pause.
+fn is_participant_call(input: &Input) -> bool {
+    matches!(
+        input,
+        Input::CastVote(..)
+            | Input::RetractVote(..)
+            | Input::ChangeVote(..)
+            | Input::CastRankedVote(..)
+            | Input::CommitVote(..)
+            | Input::DelegateVotingPower(..)
+            | Input::RevokeDelegation(..)
+    )
+}
+
+// Without the multisig the role is enough. Otherwise the caller has to be
+// one of the approvers and the action waits until it is approved by enough
+// of them.
+fn approve(action: MultisigAction, role: Role) -> Result<bool, ApiError> {
+    let serialized: Option<MultisigSerialized> = utils::key(MULTISIG_KEY);
+    match serialized.map(Multisig::deserialize) {
+        None => {
+            utils::assert_role(role);
+            Ok(true)
+        }
+        Some(mut multisig) => {
+            let approved = multisig.approve(
+                action,
+                runtime::get_caller(),
+                runtime::get_blocktime().into(),
+            )?;
+            utils::set_key(MULTISIG_KEY, Some(multisig.serialize()));
+            Ok(approved)
+        }
+    }
+}
+
+// Empty list of approvers turns the multisig off. Once the multisig is on,
+// changing or disabling it has to be approved like the removals.
+fn set_multisig(approvers: Vec<PublicKey>, threshold: u64, timeout: u64) -> Result<(), ApiError> {
+    let approvers: BTreeSet<PublicKey> = approvers.into_iter().collect();
+    let mut multisig = if approvers.is_empty() {
+        None
+    } else {
+        Some(Multisig::new(approvers.clone(), threshold, timeout)?)
+    };
+    let action = MultisigAction::SetMultisig(approvers, threshold, timeout);
+    if !approve(action, Role::Owner)? {
+        return Ok(());
+    }
+    let previous: Option<MultisigSerialized> = utils::key(MULTISIG_KEY);
+    if let (Some(multisig), Some(previous)) = (multisig.as_mut(), previous) {
+        multisig.keep_pending(Multisig::deserialize(previous));
+    }
+    utils::set_key(MULTISIG_KEY, multisig.map(|multisig| multisig.serialize()));
+    Ok(())
+}
+
+// Votes change only the caller and queries read only what they return,
+// so the other participants and the projects they don't name are not loaded.
+fn scope(input: &Input) -> Scope {
+    let caller = runtime::get_caller();
+    match input {
+        Input::CastVote(project_id, _)
+        | Input::RetractVote(project_id)
+        | Input::RevealVote(project_id, _, _) => Scope::Participant(caller, vec![*project_id]),
+        Input::ChangeVote(from_project_id, to_project_id) => {
+            Scope::Participant(caller, vec![*from_project_id, *to_project_id])
+        }
+        Input::CastRankedVote(ranking) => Scope::Participant(caller, ranking.clone()),
+        Input::CommitVote(..) => Scope::Participant(caller, Vec::new()),
+        Input::GetParticipant(public_key) | Input::GetRemainingPower(public_key) => {
+            Scope::Participant(*public_key, Vec::new())
+        }
+        Input::GetProject(project_id) => Scope::Projects(vec![*project_id]),
+        _ => Scope::All,
+    }
+}
+
+fn is_paused() -> bool {
+    utils::key(PAUSE_KEY)
+}
+
+fn pause() -> Result<(), ApiError> {
+    if is_paused() {
+        Err(Error::AlreadyPaused.into())
+    } else {
+        utils::set_key(PAUSE_KEY, true);
+        Ok(())
     }
 }
 
-fn save_voting(voting: Voting) {
-    utils::set_key(VOTING_KEY, voting.serialize());
+fn unpause() -> Result<(), ApiError> {
+    if is_paused() {
+        utils::set_key(PAUSE_KEY, false);
+        Ok(())
+    } else {
+        Err(Error::AlreadyUnpaused.into())
+    }
 }
 
-fn read_voting() -> Voting {
-    let serialized = utils::key(VOTING_KEY);
-    Voting::deserialize(serialized)
+// Projects can be fixed during the voting only when nobody can vote on them.
+fn assert_paused_if_voting_open(voting: &Voting) -> Result<(), ApiError> {
+    let now: u64 = runtime::get_blocktime().into();
+    if voting.start_at() <= now && now < voting.end_at() && !is_paused() {
+        Err(Error::NotPaused.into())
+    } else {
+        Ok(())
+    }
+}
+
+fn save_results(results: Vec<(ProjectId, u64)>) {
+    utils::set_key(RESULTS_KEY, Voting::serialize_results(&results));
+}
+
+fn serialize_ranking(ranking: Vec<ProjectId>) -> Vec<u64> {
+    ranking.iter().map(|project_id| project_id.0).collect()
 }
 
 fn call_voting_contract(args: impl ArgsParser) {
     let voting_contract = utils::destination_contract();
     runtime::call_contract::<_, ()>(voting_contract, args);
 }
+
+fn query_voting_contract<T>(answer_key: &str, args: impl ArgsParser)
+where
+    T: CLTyped + FromBytes + ToBytes,
+{
+    let voting_contract = utils::destination_contract();
+    let answer: T = runtime::call_contract(voting_contract, args);
+    utils::set_key(answer_key, answer);
+}
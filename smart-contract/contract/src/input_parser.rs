@@ -1,46 +1,272 @@
 use crate::error::Error;
-use crate::utils::{get_arg, method_name};
+use crate::utils::{get_named_arg, get_optional_named_arg, method_name, GrantableRole};
+use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
 use casperlabs_contract::contract_api::runtime;
 use casperlabs_types::account::PublicKey;
-use logic::{Project, ProjectId};
+use logic::{Commitment, Project, ProjectId, Salt, VoteMode, Voting};
 
 pub const DEPLOY: &str = "deploy";
+pub const ACCUMULATE: &str = "accumulate";
+pub const REPLACE: &str = "replace";
+pub const QUADRATIC: &str = "quadratic";
+pub const RANKED: &str = "ranked";
+pub const APPROVAL: &str = "approval";
 pub const ADD_OR_UPDATE_PARTICIPANT: &str = "add_or_update_participant";
+pub const ADD_OR_UPDATE_PARTICIPANTS: &str = "add_or_update_participants";
 pub const REMOVE_PARTICIPANT: &str = "remove_participant";
+pub const ADD_TEAM_MEMBER: &str = "add_team_member";
+pub const REMOVE_TEAM_MEMBER: &str = "remove_team_member";
 pub const ADD_OR_UPDATE_PROJECT: &str = "add_or_update_project";
+pub const ADD_OR_UPDATE_PROJECTS: &str = "add_or_update_projects";
 pub const REMOVE_PROJECT: &str = "remove_project";
+pub const SET_PROJECT_ATTRIBUTE: &str = "set_project_attribute";
+pub const DELETE_PROJECT_ATTRIBUTE: &str = "delete_project_attribute";
 pub const CAST_VOTE: &str = "cast_vote";
+pub const RETRACT_VOTE: &str = "retract_vote";
+pub const CHANGE_VOTE: &str = "change_vote";
+pub const CAST_RANKED_VOTE: &str = "cast_ranked_vote";
+pub const COMMIT_VOTE: &str = "commit_vote";
+pub const REVEAL_VOTE: &str = "reveal_vote";
+pub const DELEGATE_VOTING_POWER: &str = "delegate_voting_power";
+pub const REVOKE_DELEGATION: &str = "revoke_delegation";
+pub const PUBLISH_RESULTS: &str = "publish_results";
+pub const FINALIZE: &str = "finalize";
+pub const SET_VOTING_WINDOW: &str = "set_voting_window";
+pub const PAUSE: &str = "pause";
+pub const UNPAUSE: &str = "unpause";
+pub const PROPOSE_ADMIN: &str = "propose_admin";
+pub const ACCEPT_ADMIN: &str = "accept_admin";
+pub const GRANT_ROLE: &str = "grant_role";
+pub const REVOKE_ROLE: &str = "revoke_role";
+pub const SET_MULTISIG: &str = "set_multisig";
+pub const GET_PROJECT: &str = "get_project";
+pub const GET_PARTICIPANT: &str = "get_participant";
+pub const GET_REMAINING_POWER: &str = "get_remaining_power";
+pub const GET_RESULTS: &str = "get_results";
+pub const PROJECT_MANAGER: &str = "project_manager";
+pub const PARTICIPANT_MANAGER: &str = "participant_manager";
+
+pub mod arg {
+    pub const START_AT: &str = "start_at";
+    pub const END_AT: &str = "end_at";
+    pub const VOTE_MODE: &str = "vote_mode";
+    pub const APPROVAL_LIMIT: &str = "approval_limit";
+    pub const REVEAL_END_AT: &str = "reveal_end_at";
+    pub const PARTICIPANT: &str = "participant";
+    pub const PARTICIPANTS: &str = "participants";
+    pub const VOTING_POWER: &str = "voting_power";
+    pub const PROJECT_ID: &str = "project_id";
+    pub const PROJECTS: &str = "projects";
+    pub const NAME: &str = "name";
+    pub const TEAM: &str = "team";
+    pub const ATTRIBUTES: &str = "attributes";
+    pub const KEY: &str = "key";
+    pub const VALUE: &str = "value";
+    pub const FROM_PROJECT_ID: &str = "from_project_id";
+    pub const TO_PROJECT_ID: &str = "to_project_id";
+    pub const RANKING: &str = "ranking";
+    pub const COMMITMENT: &str = "commitment";
+    pub const SALT: &str = "salt";
+    pub const DELEGATE: &str = "delegate";
+    pub const ADMIN: &str = "admin";
+    pub const ACCOUNT: &str = "account";
+    pub const ROLE: &str = "role";
+    pub const APPROVERS: &str = "approvers";
+    pub const THRESHOLD: &str = "threshold";
+    pub const TIMEOUT: &str = "timeout";
+}
 
 // #[allow(clippy::large_enum_variant)]
 pub enum Input {
-    Deploy(u64, u64),
+    Deploy(u64, u64, VoteMode, Option<u64>),
     AddOrUpdateParticipant(PublicKey, u64),
+    AddOrUpdateParticipants(Vec<(PublicKey, u64)>),
     RemoveParticipant(PublicKey),
+    AddTeamMember(PublicKey, ProjectId),
+    RemoveTeamMember(PublicKey),
     AddOrUpdateProject(ProjectId, Project),
+    AddOrUpdateProjects(BTreeMap<ProjectId, Project>),
     RemoveProject(ProjectId),
+    SetProjectAttribute(ProjectId, String, String),
+    DeleteProjectAttribute(ProjectId, String),
     CastVote(ProjectId, u64),
+    RetractVote(ProjectId),
+    ChangeVote(ProjectId, ProjectId),
+    CastRankedVote(Vec<ProjectId>),
+    CommitVote(Commitment),
+    RevealVote(ProjectId, u64, Salt),
+    DelegateVotingPower(PublicKey, u64),
+    RevokeDelegation(PublicKey),
+    PublishResults,
+    Finalize,
+    SetVotingWindow(u64, u64),
+    Pause,
+    Unpause,
+    ProposeAdmin(PublicKey),
+    AcceptAdmin,
+    GrantRole(PublicKey, GrantableRole),
+    RevokeRole(PublicKey, GrantableRole),
+    SetMultisig(Vec<PublicKey>, u64, u64),
+    GetProject(ProjectId),
+    GetParticipant(PublicKey),
+    GetRemainingPower(PublicKey),
+    GetResults,
 }
 
 pub fn from_args() -> Input {
     let method: String = method_name();
     match method.as_str() {
-        DEPLOY => Input::Deploy(get_arg(1), get_arg(2)),
-        ADD_OR_UPDATE_PARTICIPANT => Input::AddOrUpdateParticipant(get_arg(1), get_arg(2)),
-        REMOVE_PARTICIPANT => Input::RemoveParticipant(get_arg(1)),
-        ADD_OR_UPDATE_PROJECT => Input::AddOrUpdateProject(ProjectId(get_arg(1)), read_project(2)),
-        REMOVE_PROJECT => Input::RemoveProject(ProjectId(get_arg(1))),
-        CAST_VOTE => Input::CastVote(ProjectId(get_arg(1)), get_arg(2)),
+        DEPLOY => Input::Deploy(
+            get_named_arg(arg::START_AT),
+            get_named_arg(arg::END_AT),
+            read_vote_mode(),
+            read_reveal_end(),
+        ),
+        ADD_OR_UPDATE_PARTICIPANT => Input::AddOrUpdateParticipant(
+            get_named_arg(arg::PARTICIPANT),
+            get_named_arg(arg::VOTING_POWER),
+        ),
+        ADD_OR_UPDATE_PARTICIPANTS => {
+            Input::AddOrUpdateParticipants(get_named_arg(arg::PARTICIPANTS))
+        }
+        REMOVE_PARTICIPANT => Input::RemoveParticipant(get_named_arg(arg::PARTICIPANT)),
+        ADD_TEAM_MEMBER => Input::AddTeamMember(
+            get_named_arg(arg::PARTICIPANT),
+            read_project_id(arg::PROJECT_ID),
+        ),
+        REMOVE_TEAM_MEMBER => Input::RemoveTeamMember(get_named_arg(arg::PARTICIPANT)),
+        ADD_OR_UPDATE_PROJECT => {
+            Input::AddOrUpdateProject(read_project_id(arg::PROJECT_ID), read_project())
+        }
+        ADD_OR_UPDATE_PROJECTS => {
+            Input::AddOrUpdateProjects(Voting::deserialize_projects(get_named_arg(arg::PROJECTS)))
+        }
+        REMOVE_PROJECT => Input::RemoveProject(read_project_id(arg::PROJECT_ID)),
+        SET_PROJECT_ATTRIBUTE => Input::SetProjectAttribute(
+            read_project_id(arg::PROJECT_ID),
+            get_named_arg(arg::KEY),
+            get_named_arg(arg::VALUE),
+        ),
+        DELETE_PROJECT_ATTRIBUTE => {
+            Input::DeleteProjectAttribute(read_project_id(arg::PROJECT_ID), get_named_arg(arg::KEY))
+        }
+        CAST_VOTE => Input::CastVote(
+            read_project_id(arg::PROJECT_ID),
+            get_named_arg(arg::VOTING_POWER),
+        ),
+        RETRACT_VOTE => Input::RetractVote(read_project_id(arg::PROJECT_ID)),
+        CHANGE_VOTE => Input::ChangeVote(
+            read_project_id(arg::FROM_PROJECT_ID),
+            read_project_id(arg::TO_PROJECT_ID),
+        ),
+        CAST_RANKED_VOTE => Input::CastRankedVote(read_ranking()),
+        COMMIT_VOTE => Input::CommitVote(get_named_arg(arg::COMMITMENT)),
+        REVEAL_VOTE => Input::RevealVote(
+            read_project_id(arg::PROJECT_ID),
+            get_named_arg(arg::VOTING_POWER),
+            get_named_arg(arg::SALT),
+        ),
+        DELEGATE_VOTING_POWER => Input::DelegateVotingPower(
+            get_named_arg(arg::DELEGATE),
+            get_named_arg(arg::VOTING_POWER),
+        ),
+        REVOKE_DELEGATION => Input::RevokeDelegation(get_named_arg(arg::DELEGATE)),
+        PUBLISH_RESULTS => Input::PublishResults,
+        FINALIZE => Input::Finalize,
+        SET_VOTING_WINDOW => {
+            Input::SetVotingWindow(get_named_arg(arg::START_AT), get_named_arg(arg::END_AT))
+        }
+        PAUSE => Input::Pause,
+        UNPAUSE => Input::Unpause,
+        PROPOSE_ADMIN => Input::ProposeAdmin(get_named_arg(arg::ADMIN)),
+        ACCEPT_ADMIN => Input::AcceptAdmin,
+        GRANT_ROLE => Input::GrantRole(get_named_arg(arg::ACCOUNT), read_role()),
+        REVOKE_ROLE => Input::RevokeRole(get_named_arg(arg::ACCOUNT), read_role()),
+        SET_MULTISIG => Input::SetMultisig(
+            get_named_arg(arg::APPROVERS),
+            get_named_arg(arg::THRESHOLD),
+            get_named_arg(arg::TIMEOUT),
+        ),
+        GET_PROJECT => Input::GetProject(read_project_id(arg::PROJECT_ID)),
+        GET_PARTICIPANT => Input::GetParticipant(get_named_arg(arg::PARTICIPANT)),
+        GET_REMAINING_POWER => Input::GetRemainingPower(get_named_arg(arg::PARTICIPANT)),
+        GET_RESULTS => Input::GetResults,
         _ => runtime::revert(Error::UnknownApiCommand),
     }
 }
 
-fn read_project(shift: u32) -> Project {
+// Names of the arguments to send before their values.
+pub fn arg_names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| String::from(*name)).collect()
+}
+
+fn read_vote_mode() -> VoteMode {
+    let vote_mode: String = get_named_arg(arg::VOTE_MODE);
+    match vote_mode.as_str() {
+        ACCUMULATE => VoteMode::Accumulate,
+        REPLACE => VoteMode::Replace,
+        QUADRATIC => VoteMode::Quadratic,
+        RANKED => VoteMode::Ranked,
+        APPROVAL => VoteMode::Approval(get_named_arg(arg::APPROVAL_LIMIT)),
+        _ => runtime::revert(Error::UnknownVoteMode),
+    }
+}
+
+pub fn vote_mode_name(vote_mode: VoteMode) -> &'static str {
+    match vote_mode {
+        VoteMode::Accumulate => ACCUMULATE,
+        VoteMode::Replace => REPLACE,
+        VoteMode::Quadratic => QUADRATIC,
+        VoteMode::Ranked => RANKED,
+        VoteMode::Approval(_) => APPROVAL,
+    }
+}
+
+fn read_role() -> GrantableRole {
+    let role: String = get_named_arg(arg::ROLE);
+    match role.as_str() {
+        PROJECT_MANAGER => GrantableRole::ProjectManager,
+        PARTICIPANT_MANAGER => GrantableRole::ParticipantManager,
+        _ => runtime::revert(Error::UnknownRole),
+    }
+}
+
+pub fn role_name(role: GrantableRole) -> &'static str {
+    match role {
+        GrantableRole::ProjectManager => PROJECT_MANAGER,
+        GrantableRole::ParticipantManager => PARTICIPANT_MANAGER,
+    }
+}
+
+pub fn approval_limit(vote_mode: VoteMode) -> u64 {
+    match vote_mode {
+        VoteMode::Approval(limit) => limit,
+        _ => 0,
+    }
+}
+
+// Missing or zero means there is no reveal phase and votes are public.
+fn read_reveal_end() -> Option<u64> {
+    let reveal_end_at: Option<u64> = get_optional_named_arg(arg::REVEAL_END_AT);
+    reveal_end_at.filter(|reveal_end_at| *reveal_end_at != 0)
+}
+
+fn read_project_id(name: &str) -> ProjectId {
+    ProjectId(get_named_arg(name))
+}
+
+fn read_ranking() -> Vec<ProjectId> {
+    let ranking: Vec<u64> = get_named_arg(arg::RANKING);
+    ranking.into_iter().map(ProjectId).collect()
+}
+
+// Attributes are optional.
+fn read_project() -> Project {
     Project {
-        name: get_arg(shift),
-        team_name: get_arg(shift + 1),
-        video_link: get_arg(shift + 2),
-        github_link: get_arg(shift + 3),
-        google_drive_link: get_arg(shift + 4),
+        name: get_named_arg(arg::NAME),
+        team_name: get_named_arg(arg::TEAM),
+        attributes: get_optional_named_arg(arg::ATTRIBUTES).unwrap_or_default(),
     }
 }
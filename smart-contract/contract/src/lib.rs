@@ -5,4 +5,5 @@ extern crate alloc;
 mod contracts;
 mod error;
 mod input_parser;
+mod storage;
 mod utils;
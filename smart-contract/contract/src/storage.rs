@@ -0,0 +1,190 @@
+use crate::utils;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use casperlabs_contract::contract_api::runtime;
+use casperlabs_types::{
+    account::PublicKey,
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped,
+};
+use core::fmt::Write;
+use logic::{
+    ConfigSerialized, ParticipantSerialized, ParticipantsSerialized, ProjectId, ProjectsSerialized,
+    Voting,
+};
+
+const VERSION_KEY: &str = "voting_version";
+const CONFIG_KEY: &str = "voting_config";
+const PROJECTS_KEY: &str = "voting_projects";
+const PARTICIPANTS_KEY: &str = "voting_participants";
+
+// Part of the voting that has to be loaded to handle the call.
+pub enum Scope {
+    All,
+    // One participant, the given projects and the projects the participant
+    // voted on, so its invariants can be checked.
+    Participant(PublicKey, Vec<ProjectId>),
+    // The given projects, but no participants. Only for reading.
+    Projects(Vec<ProjectId>),
+}
+
+// Every project and every participant is saved under its own named key,
+// so calls that touch one participant don't pay for reading and writing
+// all of them. Only entries that changed are written back.
+pub struct VotingStorage {
+    scope: Scope,
+    version: Option<u8>,
+    config: Option<ConfigSerialized>,
+    projects: ProjectsSerialized,
+    participants: ParticipantsSerialized,
+}
+
+impl VotingStorage {
+    pub fn new() -> VotingStorage {
+        VotingStorage {
+            scope: Scope::All,
+            version: None,
+            config: None,
+            projects: BTreeMap::new(),
+            participants: BTreeMap::new(),
+        }
+    }
+
+    pub fn load(scope: Scope) -> Result<(VotingStorage, Voting), ApiError> {
+        let version: u8 = utils::key(VERSION_KEY);
+        let config: ConfigSerialized = utils::key(CONFIG_KEY);
+        let participant_keys = match &scope {
+            Scope::All => index(PARTICIPANTS_KEY),
+            Scope::Participant(public_key, _) => {
+                existing(participant_key, vec![public_key.value()])
+            }
+            Scope::Projects(_) => Vec::new(),
+        };
+        let participants: ParticipantsSerialized = participant_keys
+            .into_iter()
+            .map(|public_key| (public_key, utils::key(&participant_key(&public_key))))
+            .collect();
+        let project_ids = match &scope {
+            Scope::All => index(PROJECTS_KEY),
+            Scope::Participant(_, project_ids) | Scope::Projects(project_ids) => {
+                let mut project_ids: BTreeSet<u64> =
+                    project_ids.iter().map(|project_id| project_id.0).collect();
+                project_ids.extend(participants.values().flat_map(voted_projects));
+                existing(project_key, project_ids.into_iter().collect())
+            }
+        };
+        let projects: ProjectsSerialized = project_ids
+            .into_iter()
+            .map(|project_id| (project_id, utils::key(&project_key(&project_id))))
+            .collect();
+        let voting =
+            Voting::deserialize((version, config, projects.clone(), participants.clone()))?;
+        let storage = VotingStorage {
+            scope,
+            version: Some(version),
+            config: Some(config),
+            projects,
+            participants,
+        };
+        Ok((storage, voting))
+    }
+
+    pub fn save(&self, voting: Voting) -> Result<(), ApiError> {
+        match &self.scope {
+            Scope::All | Scope::Projects(..) => voting.check_invariants()?,
+            Scope::Participant(public_key, _) => voting.check_participant_invariants(public_key)?,
+        }
+        let (version, config, projects, participants) = voting.serialize();
+        if self.version != Some(version) {
+            utils::set_key(VERSION_KEY, version);
+        }
+        if self.config != Some(config) {
+            utils::set_key(CONFIG_KEY, config);
+        }
+        save_entries(PROJECTS_KEY, &self.projects, projects, project_key);
+        save_entries(
+            PARTICIPANTS_KEY,
+            &self.participants,
+            participants,
+            participant_key,
+        );
+        Ok(())
+    }
+}
+
+fn save_entries<K, V>(
+    index_key: &str,
+    stored: &BTreeMap<K, V>,
+    entries: BTreeMap<K, V>,
+    key_name: fn(&K) -> String,
+) where
+    K: Ord + Copy + CLTyped + ToBytes + FromBytes,
+    V: PartialEq + CLTyped + ToBytes,
+{
+    let removed: Vec<K> = stored
+        .keys()
+        .filter(|key| !entries.contains_key(key))
+        .cloned()
+        .collect();
+    let added: Vec<K> = entries
+        .keys()
+        .filter(|key| !stored.contains_key(key))
+        .cloned()
+        .collect();
+    for key in removed.iter() {
+        runtime::remove_key(&key_name(key));
+    }
+    for (key, value) in entries {
+        if stored.get(&key) != Some(&value) {
+            utils::set_key(&key_name(&key), value);
+        }
+    }
+    if !removed.is_empty() || !added.is_empty() {
+        let mut keys = index(index_key);
+        keys.retain(|key| !removed.contains(key));
+        keys.extend(added);
+        utils::set_key(index_key, keys);
+    }
+}
+
+// Projects the participant's votes, ranking and team point to.
+fn voted_projects(participant: &ParticipantSerialized) -> Vec<u64> {
+    let ((_, team), votes, (ranking, _, _)) = participant;
+    votes
+        .keys()
+        .chain(ranking.iter())
+        .chain(team.iter())
+        .cloned()
+        .collect()
+}
+
+// Entries that are not saved are left out, instead of failing with `MissingKey`.
+fn existing<K>(key_name: fn(&K) -> String, keys: Vec<K>) -> Vec<K> {
+    keys.into_iter()
+        .filter(|key| runtime::has_key(&key_name(key)))
+        .collect()
+}
+
+fn index<K: CLTyped + FromBytes>(index_key: &str) -> Vec<K> {
+    if runtime::has_key(index_key) {
+        utils::key(index_key)
+    } else {
+        Vec::new()
+    }
+}
+
+fn project_key(project_id: &u64) -> String {
+    let mut name = String::from("project_");
+    write!(name, "{}", project_id).unwrap();
+    name
+}
+
+fn participant_key(public_key: &[u8; 32]) -> String {
+    let mut name = String::from("participant_");
+    for byte in public_key.iter() {
+        write!(name, "{:02x}", byte).unwrap();
+    }
+    name
+}
@@ -1,5 +1,7 @@
 use crate::error::Error;
+use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
 use casperlabs_contract::{
     args_parser::ArgsParser,
     contract_api::{runtime, storage},
@@ -7,12 +9,14 @@ use casperlabs_contract::{
 };
 use casperlabs_types::{
     account::PublicKey,
-    bytesrepr::{Error as ApiError, FromBytes, ToBytes},
-    CLTyped, ContractRef, Key,
+    bytesrepr::{self, FromBytes, ToBytes},
+    ApiError, CLTyped, CLValue, ContractRef, Key,
 };
 use core::convert::TryInto;
 
-const ADMIN_KEY: &str = "admin_account";
+const ROLES_KEY: &str = "roles";
+const PROPOSED_ADMIN_KEY: &str = "proposed_admin_account";
+// The `Admin` of version 1, before the roles.
 const INIT_FLAG: &str = "init_flag";
 
 pub fn deploy_code_and_init<A>(fn_name: &str, key_name: &str, init_args: A)
@@ -63,20 +67,43 @@ pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
     }
 }
 
+// Ends the call and hands the value back to the calling code.
+pub fn ret<T: CLTyped + ToBytes>(value: T) -> ! {
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+}
+
 pub fn destination_contract() -> ContractRef {
     let (_, hash): (String, [u8; 32]) = get_arg(0);
     ContractRef::Hash(hash)
 }
 
-pub fn get_arg<T: CLTyped + FromBytes>(i: u32) -> T {
+// Arguments after the method are named. The second argument is the list
+// of their names and the values follow in the same order.
+pub fn get_named_arg<T: CLTyped + FromBytes>(name: &str) -> T {
+    get_optional_named_arg(name).unwrap_or_revert_with(Error::MissingArgument)
+}
+
+pub fn get_optional_named_arg<T: CLTyped + FromBytes>(name: &str) -> Option<T> {
+    let names: Vec<String> = match runtime::get_arg(1) {
+        None => return None,
+        Some(names) => names.unwrap_or_revert_with(Error::InvalidArgument),
+    };
+    names.iter().position(|arg| arg == name).map(|i| {
+        runtime::get_arg(i as u32 + 2)
+            .unwrap_or_revert_with(Error::MissingArgument)
+            .unwrap_or_revert_with(Error::InvalidArgument)
+    })
+}
+
+fn get_arg<T: CLTyped + FromBytes>(i: u32) -> T {
     runtime::get_arg(i)
-        .unwrap_or_revert_with(Error::missing_argument(i))
-        .unwrap_or_revert_with(Error::invalid_argument(i))
+        .unwrap_or_revert_with(Error::MissingArgument)
+        .unwrap_or_revert_with(Error::InvalidArgument)
 }
 
 pub fn method_name() -> String {
-    let maybe_argument: Result<String, ApiError> =
-        runtime::get_arg(0).unwrap_or_revert_with(Error::missing_argument(0));
+    let maybe_argument: Result<String, bytesrepr::Error> =
+        runtime::get_arg(0).unwrap_or_revert_with(Error::MissingArgument);
     match maybe_argument {
         Ok(method) => method,
         Err(_) => {
@@ -88,8 +115,8 @@ pub fn method_name() -> String {
 
 pub fn init_or_handle<F, G>(init: F, handle: G)
 where
-    F: Fn() -> Result<(), Error>,
-    G: Fn() -> Result<(), Error>,
+    F: Fn() -> Result<(), ApiError>,
+    G: Fn() -> Result<(), ApiError>,
 {
     if runtime::has_key(INIT_FLAG) {
         handle().unwrap_or_revert();
@@ -99,14 +126,109 @@ where
     }
 }
 
+#[derive(Clone, Copy)]
+pub enum Role {
+    // The `Admin`. Has every other role as well.
+    Owner,
+    ProjectManager,
+    ParticipantManager,
+}
+
+impl Role {
+    fn flag(self) -> u8 {
+        match self {
+            Role::Owner => 1,
+            Role::ProjectManager => 2,
+            Role::ParticipantManager => 4,
+        }
+    }
+}
+
+// Roles of `grant_role` and `revoke_role`. The owner role is handed over
+// with `propose_admin` and `accept_admin`.
+#[derive(Clone, Copy)]
+pub enum GrantableRole {
+    ProjectManager,
+    ParticipantManager,
+}
+
+impl From<GrantableRole> for Role {
+    fn from(role: GrantableRole) -> Role {
+        match role {
+            GrantableRole::ProjectManager => Role::ProjectManager,
+            GrantableRole::ParticipantManager => Role::ParticipantManager,
+        }
+    }
+}
+
+fn roles() -> BTreeMap<PublicKey, u8> {
+    if runtime::has_key(ROLES_KEY) {
+        key(ROLES_KEY)
+    } else {
+        BTreeMap::new()
+    }
+}
+
+fn update_roles<F: Fn(u8) -> u8>(accounts: &[PublicKey], update: F) {
+    let mut roles = roles();
+    for account in accounts {
+        let flags = update(roles.get(account).cloned().unwrap_or(0));
+        if flags == 0 {
+            roles.remove(account);
+        } else {
+            roles.insert(*account, flags);
+        }
+    }
+    set_key(ROLES_KEY, roles);
+}
+
+pub fn grant_role(account: PublicKey, role: Role) {
+    update_roles(&[account], |flags| flags | role.flag());
+}
+
+pub fn revoke_role(account: PublicKey, role: Role) {
+    update_roles(&[account], |flags| flags & !role.flag());
+}
+
+pub fn has_role(account: PublicKey, role: Role) -> bool {
+    let flags = roles().get(&account).cloned().unwrap_or(0);
+    flags & (Role::Owner.flag() | role.flag()) != 0
+}
+
+pub fn assert_role(role: Role) {
+    if !has_role(runtime::get_caller(), role) {
+        match role {
+            Role::Owner => runtime::revert(Error::NotTheAdminAccount),
+            _ => runtime::revert(Error::MissingRole),
+        }
+    }
+}
+
+// There is only one `Admin`, so the previous one loses the owner role.
 pub fn set_admin_account(admin: PublicKey) {
-    set_key(ADMIN_KEY, admin);
+    let mut accounts: Vec<PublicKey> = roles().keys().cloned().collect();
+    accounts.push(admin);
+    update_roles(&accounts, |flags| flags & !Role::Owner.flag());
+    grant_role(admin, Role::Owner);
+}
+
+pub fn propose_admin_account(admin: PublicKey) {
+    set_key(PROPOSED_ADMIN_KEY, Some(admin));
 }
 
-pub fn assert_admin() {
-    let admin: PublicKey = key(ADMIN_KEY);
+// The admin role changes only when the proposed account confirms it,
+// so it can't be handed over to an account nobody controls.
+pub fn accept_admin_account() -> Result<(), Error> {
     let caller = runtime::get_caller();
-    if admin != caller {
-        runtime::revert(Error::NotTheAdminAccount);
+    let proposed: Option<PublicKey> = if runtime::has_key(PROPOSED_ADMIN_KEY) {
+        key(PROPOSED_ADMIN_KEY)
+    } else {
+        None
+    };
+    if proposed != Some(caller) {
+        return Err(Error::NotTheProposedAdminAccount);
     }
+    set_admin_account(caller);
+    set_key::<Option<PublicKey>>(PROPOSED_ADMIN_KEY, None);
+    Ok(())
 }
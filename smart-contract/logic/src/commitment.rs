@@ -0,0 +1,35 @@
+use crate::voting::ProjectId;
+use blake2::digest::{Input, VariableOutput};
+use blake2::VarBlake2b;
+
+pub const COMMITMENT_LENGTH: usize = 32;
+
+pub type Commitment = [u8; COMMITMENT_LENGTH];
+
+pub type Salt = [u8; 32];
+
+// Blake2b-256 hash of little-endian `project_id`, little-endian `vote` and `salt`.
+pub fn vote_commitment(project_id: ProjectId, vote: u64, salt: Salt) -> Commitment {
+    let mut hasher = VarBlake2b::new(COMMITMENT_LENGTH).unwrap();
+    hasher.input(project_id.0.to_le_bytes());
+    hasher.input(vote.to_le_bytes());
+    hasher.input(salt);
+    let mut output = [0u8; COMMITMENT_LENGTH];
+    hasher.variable_result(|hash| output.copy_from_slice(hash));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vote_commitment() {
+        let salt = [7u8; 32];
+        let commitment = vote_commitment(ProjectId(1), 10, salt);
+        assert_eq!(commitment, vote_commitment(ProjectId(1), 10, salt));
+        assert_ne!(commitment, vote_commitment(ProjectId(2), 10, salt));
+        assert_ne!(commitment, vote_commitment(ProjectId(1), 11, salt));
+        assert_ne!(commitment, vote_commitment(ProjectId(1), 10, [8u8; 32]));
+    }
+}
@@ -1,11 +1,133 @@
-#[derive(PartialEq, Debug)]
-pub struct StartNotBeforeEnd;
-
-#[derive(PartialEq, Debug)]
-pub enum VotingError {
-    NotEnoughVotingPower,
-    ProjectDoesNotExists,
-    NotAParticipant,
-    VotingNotStarted,
-    VotingEnded,
+use crate::voting::ProjectId;
+use casperlabs_types::ApiError;
+
+// Defines the error enums together with their `ApiError::User` codes and
+// the `ERROR_CODES` table, so the codes can't get out of sync with the enums.
+// The codes are stable: never change or reuse them.
+macro_rules! errors {
+    ($(
+        $error:ident {
+            $($name:ident $({ $($field:ident: $ty:ty),* })? = $code:expr,)*
+        }
+    )*) => {
+        $(
+            #[derive(PartialEq, Debug)]
+            pub enum $error {
+                $($name $({ $($field: $ty),* })?,)*
+            }
+
+            impl $error {
+                pub fn code(&self) -> u16 {
+                    match self {
+                        $($error::$name { .. } => $code,)*
+                    }
+                }
+
+                pub fn name(&self) -> &'static str {
+                    match self {
+                        $($error::$name { .. } => stringify!($name),)*
+                    }
+                }
+            }
+
+            impl From<$error> for ApiError {
+                fn from(error: $error) -> ApiError {
+                    ApiError::User(error.code())
+                }
+            }
+        )*
+
+        // `(code, name)` of every error the contract can revert with.
+        pub const ERROR_CODES: &[(u16, &str)] = &[$($(($code, stringify!($name)),)*)*];
+    };
+}
+
+errors! {
+    ContractError {
+        // Commands and arguments: 100 - 199.
+        UnknownApiCommand = 100,
+        UnknownDeployCommand = 101,
+        UnknownIndirectCommand = 102,
+        UnknownInitCommand = 103,
+        UnknownContractCommand = 104,
+        UnknownVoteMode = 105,
+        UnknownRole = 106,
+        MissingArgument = 107,
+        InvalidArgument = 108,
+        // Authorization: 200 - 299.
+        NotTheAdminAccount = 200,
+        NotTheProposedAdminAccount = 201,
+        MissingRole = 202,
+        // State of the contract: 400 - 499.
+        AlreadyPaused = 400,
+        AlreadyUnpaused = 401,
+        NotPaused = 402,
+        VotingPaused = 403,
+        MissingKey = 404,
+        UnexpectedType = 405,
+    }
+
+    VotingError {
+        // Commands and arguments: 100 - 199.
+        InvalidThreshold = 109,
+        // Authorization: 200 - 299.
+        NotAnApprover = 203,
+        // Voting logic: 300 - 399.
+        StartNotBeforeEnd = 300,
+        NotEnoughVotingPower { required: u64, available: u64 } = 301,
+        ProjectDoesNotExists { project_id: ProjectId } = 302,
+        NotAParticipant = 303,
+        VotingNotStarted = 304,
+        VotingEnded = 305,
+        VotingNotEnded = 306,
+        VotingFinalized = 307,
+        VoteDoesNotExist = 308,
+        InvariantViolated = 309,
+        WrongVoteMode = 310,
+        DuplicatedProject = 311,
+        TooManyApprovals { limit: u64 } = 312,
+        InvalidApproval = 313,
+        RevealNotStarted = 314,
+        RevealEnded = 315,
+        CommitmentDoesNotMatch = 316,
+        DelegationCycle = 317,
+        DelegationDoesNotExist = 318,
+        CannotVoteForOwnProject = 319,
+        EndInThePast = 320,
+        ReservedAttribute = 321,
+        AttributeDoesNotExist = 322,
+        EmptyProjectName = 323,
+        FieldTooLong { length: usize } = 324,
+        InvalidLink = 325,
+        TooManyAttributes = 326,
+        EmptyAttributeName = 327,
+        RevealEndNotAfterEnd = 328,
+        // State of the contract: 400 - 499.
+        UnsupportedVersion = 406,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        let error = VotingError::NotEnoughVotingPower {
+            required: 2,
+            available: 1,
+        };
+        assert_eq!(error.code(), 301);
+        assert_eq!(error.name(), "NotEnoughVotingPower");
+        assert_eq!(ContractError::MissingArgument.code(), 107);
+        assert!(ERROR_CODES.contains(&(300, "StartNotBeforeEnd")));
+        assert!(ERROR_CODES.contains(&(400, "AlreadyPaused")));
+        assert_eq!(VotingError::NotAnApprover.code(), 203);
+        assert_eq!(VotingError::InvalidThreshold.code(), 109);
+
+        // Every code is used once.
+        for (i, (code, _)) in ERROR_CODES.iter().enumerate() {
+            assert!(ERROR_CODES[i + 1..].iter().all(|(other, _)| other != code));
+        }
+    }
 }
@@ -0,0 +1,111 @@
+use crate::voting::ProjectId;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+// Orders candidates by running instant-runoff rounds until none is left.
+// In every round each ballot counts for its highest ranked candidate that is
+// still in the race and the candidate with the fewest votes is eliminated.
+// Ties are resolved by eliminating the higher project id first.
+// Returns the winner first, together with the votes each candidate had
+// in the round it was eliminated in.
+pub fn instant_runoff(
+    mut candidates: BTreeSet<ProjectId>,
+    ballots: &[&Vec<ProjectId>],
+) -> Vec<(ProjectId, u64)> {
+    let mut eliminated = Vec::new();
+    while let Some((loser, votes)) = weakest_candidate(&candidates, ballots) {
+        candidates.remove(&loser);
+        eliminated.push((loser, votes));
+    }
+    eliminated.reverse();
+    eliminated
+}
+
+fn weakest_candidate(
+    candidates: &BTreeSet<ProjectId>,
+    ballots: &[&Vec<ProjectId>],
+) -> Option<(ProjectId, u64)> {
+    let mut counts: BTreeMap<ProjectId, u64> = BTreeMap::new();
+    for candidate in candidates.iter() {
+        counts.insert(*candidate, 0);
+    }
+    for ballot in ballots.iter() {
+        let choice = ballot
+            .iter()
+            .find(|project_id| candidates.contains(project_id));
+        if let Some(project_id) = choice {
+            if let Some(count) = counts.get_mut(project_id) {
+                *count += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .min_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const A: ProjectId = ProjectId(1);
+    const B: ProjectId = ProjectId(2);
+    const C: ProjectId = ProjectId(3);
+
+    fn candidates() -> BTreeSet<ProjectId> {
+        let mut output = BTreeSet::new();
+        output.insert(A);
+        output.insert(B);
+        output.insert(C);
+        output
+    }
+
+    #[test]
+    fn test_no_candidates() {
+        let ballot = vec![A];
+        assert!(instant_runoff(BTreeSet::new(), &[&ballot]).is_empty());
+    }
+
+    #[test]
+    fn test_majority_winner() {
+        let first = vec![A, B];
+        let second = vec![A, C];
+        let third = vec![B];
+        assert_eq!(
+            instant_runoff(candidates(), &[&first, &second, &third]),
+            vec![(A, 2), (B, 1), (C, 0)]
+        );
+    }
+
+    #[test]
+    fn test_votes_are_transferred() {
+        // A leads in the first round, but C is eliminated and its votes go to B.
+        let first = vec![A];
+        let second = vec![A];
+        let third = vec![B];
+        let fourth = vec![B, A];
+        let fifth = vec![C, B];
+        let sixth = vec![C, B];
+        let seventh = vec![A];
+        assert_eq!(
+            instant_runoff(
+                candidates(),
+                &[&first, &second, &third, &fourth, &fifth, &sixth, &seventh]
+            ),
+            vec![(B, 4), (A, 3), (C, 2)]
+        );
+    }
+
+    #[test]
+    fn test_exhausted_ballots() {
+        let first = vec![C];
+        let second = vec![A, B];
+        let third = vec![B];
+        let fourth = vec![B];
+        assert_eq!(
+            instant_runoff(candidates(), &[&first, &second, &third, &fourth]),
+            vec![(B, 3), (A, 1), (C, 1)]
+        );
+    }
+}
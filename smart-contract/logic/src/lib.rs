@@ -2,8 +2,17 @@
 
 extern crate alloc;
 
+mod commitment;
 mod error;
+mod instant_runoff;
+mod multisig;
 mod voting;
 
-pub use error::{StartNotBeforeEnd, VotingError};
-pub use voting::{Participant, Project, ProjectId, Voting};
+pub use commitment::{vote_commitment, Commitment, Salt};
+pub use error::{ContractError, VotingError, ERROR_CODES};
+pub use multisig::{Multisig, MultisigAction, MultisigSerialized, PendingAction};
+pub use voting::{
+    ConfigSerialized, Participant, ParticipantSerialized, ParticipantsSerialized, Project,
+    ProjectId, ProjectSerialized, ProjectsSerialized, ResultsSerialized, VoteMode, Voting,
+    VotingSerialized, MAX_ATTRIBUTES, MAX_FIELD_LENGTH, VERSION,
+};
@@ -0,0 +1,298 @@
+use crate::error::VotingError;
+use crate::voting::ProjectId;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use casperlabs_types::account::PublicKey;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum MultisigAction {
+    RemoveProject(ProjectId),
+    RemoveParticipant(PublicKey),
+    // (approvers, threshold, timeout) of the new multisig.
+    // No approvers turn the multisig off.
+    SetMultisig(BTreeSet<PublicKey>, u64, u64),
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PendingAction {
+    pub created_at: u64,
+    pub approvals: BTreeSet<PublicKey>,
+}
+
+// Actions that take effect only after `threshold` of `approvers` agreed on
+// them. Approvals are collected for `timeout` since the first one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Multisig {
+    pub approvers: BTreeSet<PublicKey>,
+    pub threshold: u64,
+    pub timeout: u64,
+    pub pending: BTreeMap<MultisigAction, PendingAction>,
+}
+
+type PendingSerialized<K> = BTreeMap<K, (u64, Vec<[u8; 32]>)>;
+
+// (approvers, threshold, timeout)
+type ConfigSerialized = (Vec<[u8; 32]>, u64, u64);
+
+pub type MultisigSerialized = (
+    ConfigSerialized,
+    // Pending project removals.
+    PendingSerialized<u64>,
+    // Pending participant removals.
+    PendingSerialized<[u8; 32]>,
+    // Pending changes of the multisig.
+    PendingSerialized<ConfigSerialized>,
+);
+
+impl Multisig {
+    pub fn new(
+        approvers: BTreeSet<PublicKey>,
+        threshold: u64,
+        timeout: u64,
+    ) -> Result<Multisig, VotingError> {
+        if threshold == 0 || threshold > approvers.len() as u64 {
+            Err(VotingError::InvalidThreshold)
+        } else {
+            Ok(Multisig {
+                approvers,
+                threshold,
+                timeout,
+                pending: BTreeMap::new(),
+            })
+        }
+    }
+
+    // Returns `true` when the action got enough approvals and should be executed.
+    pub fn approve(
+        &mut self,
+        action: MultisigAction,
+        approver: PublicKey,
+        now: u64,
+    ) -> Result<bool, VotingError> {
+        if !self.approvers.contains(&approver) {
+            return Err(VotingError::NotAnApprover);
+        }
+        self.remove_expired(now);
+        let approvals = {
+            let pending = self.pending.entry(action.clone()).or_insert(PendingAction {
+                created_at: now,
+                approvals: BTreeSet::new(),
+            });
+            pending.approvals.insert(approver);
+            pending.approvals.len() as u64
+        };
+        if approvals >= self.threshold {
+            self.pending.remove(&action);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    // Keeps the pending actions of the previous multisig, with the approvals
+    // of the approvers that are still on the list.
+    pub fn keep_pending(&mut self, previous: Multisig) {
+        for (action, mut pending) in previous.pending {
+            pending
+                .approvals
+                .retain(|approver| self.approvers.contains(approver));
+            if !pending.approvals.is_empty() {
+                self.pending.insert(action, pending);
+            }
+        }
+    }
+
+    fn remove_expired(&mut self, now: u64) {
+        let timeout = self.timeout;
+        let expired: Vec<MultisigAction> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.created_at.saturating_add(timeout) <= now)
+            .map(|(action, _)| action.clone())
+            .collect();
+        for action in expired {
+            self.pending.remove(&action);
+        }
+    }
+
+    pub fn serialize(&self) -> MultisigSerialized {
+        let mut projects = BTreeMap::new();
+        let mut participants = BTreeMap::new();
+        let mut multisigs = BTreeMap::new();
+        for (action, pending) in self.pending.iter() {
+            let value = (
+                pending.created_at,
+                pending
+                    .approvals
+                    .iter()
+                    .map(|public_key| public_key.value())
+                    .collect(),
+            );
+            match action {
+                MultisigAction::RemoveProject(project_id) => {
+                    projects.insert(project_id.0, value);
+                }
+                MultisigAction::RemoveParticipant(public_key) => {
+                    participants.insert(public_key.value(), value);
+                }
+                MultisigAction::SetMultisig(approvers, threshold, timeout) => {
+                    multisigs.insert(serialize_config(approvers, *threshold, *timeout), value);
+                }
+            }
+        }
+        (
+            serialize_config(&self.approvers, self.threshold, self.timeout),
+            projects,
+            participants,
+            multisigs,
+        )
+    }
+
+    pub fn deserialize(value: MultisigSerialized) -> Multisig {
+        let ((approvers, threshold, timeout), projects, participants, multisigs) = value;
+        let mut pending = BTreeMap::new();
+        for (project_id, (created_at, approvals)) in projects {
+            pending.insert(
+                MultisigAction::RemoveProject(ProjectId(project_id)),
+                Multisig::deserialize_pending(created_at, approvals),
+            );
+        }
+        for (public_key, (created_at, approvals)) in participants {
+            pending.insert(
+                MultisigAction::RemoveParticipant(PublicKey::ed25519_from(public_key)),
+                Multisig::deserialize_pending(created_at, approvals),
+            );
+        }
+        for ((approvers, threshold, timeout), (created_at, approvals)) in multisigs {
+            pending.insert(
+                MultisigAction::SetMultisig(deserialize_keys(approvers), threshold, timeout),
+                Multisig::deserialize_pending(created_at, approvals),
+            );
+        }
+        Multisig {
+            approvers: deserialize_keys(approvers),
+            threshold,
+            timeout,
+            pending,
+        }
+    }
+
+    fn deserialize_pending(created_at: u64, approvals: Vec<[u8; 32]>) -> PendingAction {
+        PendingAction {
+            created_at,
+            approvals: deserialize_keys(approvals),
+        }
+    }
+}
+
+fn serialize_config(
+    approvers: &BTreeSet<PublicKey>,
+    threshold: u64,
+    timeout: u64,
+) -> ConfigSerialized {
+    (
+        approvers
+            .iter()
+            .map(|public_key| public_key.value())
+            .collect(),
+        threshold,
+        timeout,
+    )
+}
+
+fn deserialize_keys(keys: Vec<[u8; 32]>) -> BTreeSet<PublicKey> {
+    keys.into_iter().map(PublicKey::ed25519_from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const ALI: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+    pub const BOB: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+    pub const JON: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+
+    fn approvers() -> BTreeSet<PublicKey> {
+        let mut approvers = BTreeSet::new();
+        approvers.insert(ALI);
+        approvers.insert(BOB);
+        approvers
+    }
+
+    #[test]
+    fn test_init() {
+        assert_eq!(
+            Multisig::new(approvers(), 0, 10).unwrap_err(),
+            VotingError::InvalidThreshold
+        );
+        assert_eq!(
+            Multisig::new(approvers(), 3, 10).unwrap_err(),
+            VotingError::InvalidThreshold
+        );
+        assert!(Multisig::new(approvers(), 2, 10).is_ok());
+    }
+
+    #[test]
+    fn test_approve() {
+        let mut multisig = Multisig::new(approvers(), 2, 10).unwrap();
+        let action = MultisigAction::RemoveProject(ProjectId(1));
+        assert_eq!(
+            multisig.approve(action.clone(), JON, 1).unwrap_err(),
+            VotingError::NotAnApprover
+        );
+
+        // Approving twice doesn't count.
+        assert!(!multisig.approve(action.clone(), ALI, 1).unwrap());
+        assert!(!multisig.approve(action.clone(), ALI, 2).unwrap());
+        assert!(multisig.approve(action, BOB, 3).unwrap());
+        assert!(multisig.pending.is_empty());
+    }
+
+    #[test]
+    fn test_approval_expires() {
+        let mut multisig = Multisig::new(approvers(), 2, 10).unwrap();
+        let action = MultisigAction::RemoveParticipant(JON);
+        assert!(!multisig.approve(action.clone(), ALI, 1).unwrap());
+        assert!(!multisig.approve(action.clone(), BOB, 11).unwrap());
+        assert_eq!(multisig.pending.get(&action).unwrap().created_at, 11);
+        assert!(multisig.approve(action, ALI, 20).unwrap());
+    }
+
+    #[test]
+    fn test_keep_pending() {
+        let mut previous = Multisig::new(approvers(), 2, 10).unwrap();
+        let remove_project = MultisigAction::RemoveProject(ProjectId(1));
+        let remove_participant = MultisigAction::RemoveParticipant(JON);
+        previous.approve(remove_project.clone(), ALI, 1).unwrap();
+        previous
+            .approve(remove_participant.clone(), BOB, 1)
+            .unwrap();
+
+        // Approvals of the removed approvers are dropped.
+        let mut approvers = BTreeSet::new();
+        approvers.insert(ALI);
+        approvers.insert(JON);
+        let mut multisig = Multisig::new(approvers, 2, 10).unwrap();
+        multisig.keep_pending(previous);
+        assert!(multisig.pending.contains_key(&remove_project));
+        assert!(!multisig.pending.contains_key(&remove_participant));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut multisig = Multisig::new(approvers(), 2, 10).unwrap();
+        multisig
+            .approve(MultisigAction::RemoveProject(ProjectId(1)), ALI, 1)
+            .unwrap();
+        multisig
+            .approve(MultisigAction::RemoveParticipant(JON), BOB, 2)
+            .unwrap();
+        multisig
+            .approve(MultisigAction::SetMultisig(approvers(), 1, 5), ALI, 3)
+            .unwrap();
+
+        let serialized = multisig.serialize();
+        let deserialized = Multisig::deserialize(serialized);
+        assert_eq!(multisig, deserialized);
+    }
+}
@@ -1,8 +1,13 @@
+use crate::commitment::{vote_commitment, Commitment, Salt};
 use crate::error::*;
-use alloc::collections::BTreeMap;
+use crate::instant_runoff::instant_runoff;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 use casperlabs_types::account::PublicKey;
 use core::cmp::{Eq, Ord, PartialEq, PartialOrd};
+use core::mem;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct ProjectId(pub u64);
@@ -12,48 +17,232 @@ pub struct Participant {
     pub total_voting_power: u64,
     pub used_voting_power: u64,
     pub votes: BTreeMap<ProjectId, u64>,
+    pub ranking: Vec<ProjectId>,
+    // Unrevealed commitment of the secret ballot.
+    pub commitment: Option<Commitment>,
+    pub delegations: BTreeMap<PublicKey, u64>,
+    pub received_voting_power: u64,
+    pub team: Option<ProjectId>,
+}
+
+impl Participant {
+    pub fn delegated_voting_power(&self) -> u64 {
+        self.delegations
+            .values()
+            .fold(0, |sum, power| sum.saturating_add(*power))
+    }
+
+    // Own power plus power received from others, minus power delegated away.
+    pub fn voting_power(&self) -> u64 {
+        self.total_voting_power
+            .saturating_add(self.received_voting_power)
+            .saturating_sub(self.delegated_voting_power())
+    }
+
+    // Voting power that is neither used for votes nor delegated away.
+    pub fn remaining_voting_power(&self) -> u64 {
+        self.voting_power().saturating_sub(self.used_voting_power)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Project {
     pub name: String,
     pub team_name: String,
-    pub video_link: String,
-    pub github_link: String,
-    pub google_drive_link: String,
+    // Any other metadata, e.g. links to the video, the demo or the slides.
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum VoteMode {
+    // Voting again on the same project adds to the previous vote.
+    Accumulate,
+    // Voting again on the same project replaces the previous vote.
+    Replace,
+    // Votes accumulate, but `n` votes on a project cost `n^2` voting power.
+    Quadratic,
+    // Participants submit ordered lists of projects, counted by instant-runoff.
+    Ranked,
+    // Participants approve up to the given number of projects, one vote each.
+    Approval(u64),
+}
+
+impl VoteMode {
+    pub fn cost(self, votes: u64) -> u64 {
+        match self {
+            VoteMode::Quadratic => votes.saturating_mul(votes),
+            _ => votes,
+        }
+    }
+
+    fn combine(self, previous_vote: u64, vote: u64) -> u64 {
+        match self {
+            VoteMode::Replace => vote,
+            VoteMode::Approval(_) => 1,
+            _ => previous_vote.saturating_add(vote),
+        }
+    }
+
+    // Reports the voting power the vote needs on top of the already used one
+    // and the voting power that is left.
+    fn check_budget(
+        self,
+        used_voting_power: u64,
+        new_used_voting_power: u64,
+        total_voting_power: u64,
+    ) -> Result<(), VotingError> {
+        match self {
+            VoteMode::Approval(limit) if new_used_voting_power > limit => {
+                Err(VotingError::TooManyApprovals { limit })
+            }
+            VoteMode::Approval(_) => Ok(()),
+            _ if new_used_voting_power > total_voting_power => {
+                Err(VotingError::NotEnoughVotingPower {
+                    required: new_used_voting_power.saturating_sub(used_voting_power),
+                    available: total_voting_power.saturating_sub(used_voting_power),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Voting {
     pub start_timestamp: u64,
     pub end_timestamp: u64,
+    pub reveal_end_timestamp: Option<u64>,
+    pub finalized: bool,
+    pub vote_mode: VoteMode,
     pub projects: BTreeMap<ProjectId, Project>,
     pub participants: BTreeMap<PublicKey, Participant>,
 }
 
-type ProjectsSerialized = BTreeMap<u64, [String; 5]>;
+// Version of the serialized layout. Bump it with every change of the layout.
+pub const VERSION: u8 = 2;
+
+// Names of the core project fields in `ProjectSerialized`.
+// Attributes can't use them.
+const NAME: &str = "name";
+const TEAM: &str = "team";
+
+// Links of the project, kept as attributes.
+const VIDEO: &str = "video";
+const GITHUB: &str = "github";
+const GOOGLE_DRIVE: &str = "google_drive";
+
+// Project as a map of named fields: the core fields and the attributes.
+pub type ProjectSerialized = BTreeMap<String, String>;
+
+pub type ProjectsSerialized = BTreeMap<u64, ProjectSerialized>;
+
+// Maximum length in bytes of every project field, attribute names included.
+pub const MAX_FIELD_LENGTH: usize = 256;
+
+// Maximum number of the attributes of a project.
+pub const MAX_ATTRIBUTES: usize = 16;
+
+// Attributes that have to be `http(s)` URLs.
+const LINKS: [&str; 3] = [VIDEO, GITHUB, GOOGLE_DRIVE];
+
+fn validate_project(project: &Project) -> Result<(), VotingError> {
+    if project.name.is_empty() {
+        return Err(VotingError::EmptyProjectName);
+    }
+    validate_length(&project.name)?;
+    validate_length(&project.team_name)?;
+    if project.attributes.len() > MAX_ATTRIBUTES {
+        return Err(VotingError::TooManyAttributes);
+    }
+    for (key, value) in project.attributes.iter() {
+        validate_attribute(key, value)?;
+    }
+    Ok(())
+}
+
+fn validate_attribute(key: &str, value: &str) -> Result<(), VotingError> {
+    if key == NAME || key == TEAM {
+        return Err(VotingError::ReservedAttribute);
+    }
+    if key.is_empty() {
+        return Err(VotingError::EmptyAttributeName);
+    }
+    validate_length(key)?;
+    validate_length(value)?;
+    if LINKS.contains(&key) && !is_link(value) {
+        return Err(VotingError::InvalidLink);
+    }
+    Ok(())
+}
+
+fn validate_length(field: &str) -> Result<(), VotingError> {
+    if field.len() > MAX_FIELD_LENGTH {
+        Err(VotingError::FieldTooLong {
+            length: field.len(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn is_link(value: &str) -> bool {
+    let mut parts = value.splitn(2, "://");
+    match (parts.next(), parts.next()) {
+        (Some("http"), Some(rest)) | (Some("https"), Some(rest)) => {
+            !rest.is_empty() && !rest.contains(char::is_whitespace)
+        }
+        _ => false,
+    }
+}
+
+pub type ParticipantSerialized = (
+    // ((total_voting_power, used_voting_power, received_voting_power), team)
+    ((u64, u64, u64), Option<u64>),
+    // Votes
+    BTreeMap<u64, u64>,
+    // (ranking, commitment, delegations)
+    (Vec<u64>, Option<Commitment>, BTreeMap<[u8; 32], u64>),
+);
+
+pub type ParticipantsSerialized = BTreeMap<[u8; 32], ParticipantSerialized>;
 
-type ParticipantsSerialized = BTreeMap<[u8; 32], (u64, u64, BTreeMap<u64, u64>)>;
+// (project_id, votes) ordered from the winner.
+pub type ResultsSerialized = Vec<(u64, u64)>;
 
-type VotingSerialized = (
-    // (start, end)
-    (u64, u64),
-    // Projects - [name, team_name, video_ling, github_link, google_drive_link]
+// ((start, end, reveal_end), finalized, (vote_mode, approval_limit))
+pub type ConfigSerialized = ((u64, u64, Option<u64>), bool, (u8, u64));
+
+pub type VotingSerialized = (
+    // Version
+    u8,
+    ConfigSerialized,
+    // Projects
     ProjectsSerialized,
     // Participants
     ParticipantsSerialized,
 );
 
 impl Voting {
-    pub fn new(start_timestamp: u64, end_timestamp: u64) -> Result<Voting, StartNotBeforeEnd> {
+    pub fn new(start_timestamp: u64, end_timestamp: u64) -> Result<Voting, VotingError> {
+        Voting::with_vote_mode(start_timestamp, end_timestamp, VoteMode::Accumulate)
+    }
+
+    pub fn with_vote_mode(
+        start_timestamp: u64,
+        end_timestamp: u64,
+        vote_mode: VoteMode,
+    ) -> Result<Voting, VotingError> {
         if end_timestamp <= start_timestamp {
-            Err(StartNotBeforeEnd)
+            Err(VotingError::StartNotBeforeEnd)
         } else {
             Ok(Voting {
                 participants: BTreeMap::new(),
                 projects: BTreeMap::new(),
                 start_timestamp,
                 end_timestamp,
+                reveal_end_timestamp: None,
+                finalized: false,
+                vote_mode,
             })
         }
     }
@@ -66,12 +255,96 @@ impl Voting {
         self.end_timestamp
     }
 
-    pub fn add_or_update_participant(&mut self, public_key: PublicKey, total_voting_power: u64) {
-        match self.participants.get_mut(&public_key) {
+    pub fn reveal_end_at(&self) -> Option<u64> {
+        self.reveal_end_timestamp
+    }
+
+    pub fn enable_secret_ballot(&mut self, reveal_end_timestamp: u64) -> Result<(), VotingError> {
+        if self.vote_mode == VoteMode::Ranked {
+            Err(VotingError::WrongVoteMode)
+        } else if reveal_end_timestamp <= self.end_timestamp {
+            Err(VotingError::RevealEndNotAfterEnd)
+        } else {
+            self.reveal_end_timestamp = Some(reveal_end_timestamp);
+            Ok(())
+        }
+    }
+
+    pub fn set_voting_window(
+        &mut self,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        now: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        let reveal_end_timestamp = self.reveal_end_timestamp.unwrap_or(u64::MAX);
+        if end_timestamp <= start_timestamp {
+            return Err(VotingError::StartNotBeforeEnd);
+        }
+        if reveal_end_timestamp <= end_timestamp {
+            return Err(VotingError::RevealEndNotAfterEnd);
+        }
+        if end_timestamp <= now && self.has_votes() {
+            return Err(VotingError::EndInThePast);
+        }
+        self.start_timestamp = start_timestamp;
+        self.end_timestamp = end_timestamp;
+        Ok(())
+    }
+
+    fn has_votes(&self) -> bool {
+        self.participants.values().any(|participant| {
+            !participant.votes.is_empty()
+                || !participant.ranking.is_empty()
+                || participant.commitment.is_some()
+        })
+    }
+
+    pub fn is_secret_ballot(&self) -> bool {
+        self.reveal_end_timestamp.is_some()
+    }
+
+    // Votes can be counted once the reveal phase of the secret ballot is over.
+    fn closed_at(&self) -> u64 {
+        self.reveal_end_timestamp.unwrap_or(self.end_timestamp)
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.finalized
+    }
+
+    pub fn finalize(&mut self, now: u64) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        if now < self.closed_at() {
+            Err(VotingError::VotingNotEnded)
+        } else {
+            self.finalized = true;
+            Ok(())
+        }
+    }
+
+    fn assert_not_finalized(&self) -> Result<(), VotingError> {
+        if self.finalized {
+            Err(VotingError::VotingFinalized)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn add_or_update_participant(
+        &mut self,
+        public_key: PublicKey,
+        total_voting_power: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        let delegations = match self.participants.get_mut(&public_key) {
             Some(participant) => {
                 participant.total_voting_power = total_voting_power;
                 participant.used_voting_power = 0;
                 participant.votes = BTreeMap::new();
+                participant.ranking = Vec::new();
+                participant.commitment = None;
+                mem::take(&mut participant.delegations)
             }
             None => {
                 self.participants.insert(
@@ -80,30 +353,159 @@ impl Voting {
                         total_voting_power,
                         used_voting_power: 0,
                         votes: BTreeMap::new(),
+                        ranking: Vec::new(),
+                        commitment: None,
+                        delegations: BTreeMap::new(),
+                        received_voting_power: 0,
+                        team: None,
                     },
                 );
+                BTreeMap::new()
+            }
+        };
+        for (delegate, voting_power) in delegations {
+            self.release_voting_power(delegate, voting_power);
+        }
+        Ok(())
+    }
+
+    pub fn add_or_update_participants(
+        &mut self,
+        participants: Vec<(PublicKey, u64)>,
+    ) -> Result<(), VotingError> {
+        for (public_key, total_voting_power) in participants {
+            self.add_or_update_participant(public_key, total_voting_power)?;
+        }
+        Ok(())
+    }
+
+    pub fn remove_participant_if_exists(
+        &mut self,
+        public_key: &PublicKey,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        if let Some(participant) = self.participants.remove(public_key) {
+            // Power delegated to the removed participant goes back to the delegators.
+            for (_, delegator) in self.participants.iter_mut() {
+                delegator.delegations.remove(public_key);
+            }
+            for (delegate, voting_power) in participant.delegations {
+                self.release_voting_power(delegate, voting_power);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add_team_member(
+        &mut self,
+        public_key: PublicKey,
+        project_id: ProjectId,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        if !self.projects.contains_key(&project_id) {
+            return Err(VotingError::ProjectDoesNotExists { project_id });
+        }
+        match self.participants.get_mut(&public_key) {
+            None => Err(VotingError::NotAParticipant),
+            Some(participant) => {
+                // Votes already cast for the own project are cancelled.
+                if let Some(vote) = participant.votes.remove(&project_id) {
+                    participant.used_voting_power -= self.vote_mode.cost(vote);
+                }
+                participant.ranking.retain(|id| *id != project_id);
+                participant.team = Some(project_id);
+                Ok(())
             }
         }
     }
 
-    pub fn remove_participant_if_exists(&mut self, public_key: &PublicKey) {
-        self.participants.remove(public_key);
+    pub fn remove_team_member(&mut self, public_key: PublicKey) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        match self.participants.get_mut(&public_key) {
+            None => Err(VotingError::NotAParticipant),
+            Some(participant) => {
+                participant.team = None;
+                Ok(())
+            }
+        }
     }
 
-    pub fn add_or_update_project(&mut self, project_id: ProjectId, project: Project) {
+    pub fn add_or_update_project(
+        &mut self,
+        project_id: ProjectId,
+        project: Project,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        validate_project(&project)?;
         self.projects.insert(project_id, project);
+        Ok(())
+    }
+
+    pub fn set_project_attribute(
+        &mut self,
+        project_id: ProjectId,
+        key: String,
+        value: String,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        validate_attribute(&key, &value)?;
+        let project = self
+            .projects
+            .get_mut(&project_id)
+            .ok_or(VotingError::ProjectDoesNotExists { project_id })?;
+        if !project.attributes.contains_key(&key) && project.attributes.len() >= MAX_ATTRIBUTES {
+            return Err(VotingError::TooManyAttributes);
+        }
+        project.attributes.insert(key, value);
+        Ok(())
+    }
+
+    pub fn delete_project_attribute(
+        &mut self,
+        project_id: ProjectId,
+        key: &str,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        let project = self
+            .projects
+            .get_mut(&project_id)
+            .ok_or(VotingError::ProjectDoesNotExists { project_id })?;
+        project
+            .attributes
+            .remove(key)
+            .map(|_| ())
+            .ok_or(VotingError::AttributeDoesNotExist)
+    }
+
+    pub fn add_or_update_projects(
+        &mut self,
+        projects: BTreeMap<ProjectId, Project>,
+    ) -> Result<(), VotingError> {
+        for (project_id, project) in projects {
+            self.add_or_update_project(project_id, project)?;
+        }
+        Ok(())
     }
 
-    pub fn remove_project_if_exists_and_cancel_votes(&mut self, project_id: ProjectId) {
+    pub fn remove_project_if_exists_and_cancel_votes(
+        &mut self,
+        project_id: ProjectId,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
         let result = self.projects.remove(&project_id);
         if result.is_some() {
             for (_, participant) in self.participants.iter_mut() {
                 let vote = (*participant).votes.remove(&project_id);
                 if let Some(value) = vote {
-                    (*participant).used_voting_power -= value;
+                    (*participant).used_voting_power -= self.vote_mode.cost(value);
+                }
+                participant.ranking.retain(|id| *id != project_id);
+                if participant.team == Some(project_id) {
+                    participant.team = None;
                 }
             }
         }
+        Ok(())
     }
 
     pub fn cast_vote(
@@ -113,22 +515,45 @@ impl Voting {
         vote: u64,
         vote_at: u64,
     ) -> Result<(), VotingError> {
-        if vote_at < self.start_timestamp {
-            return Err(VotingError::VotingNotStarted);
-        }
-        if vote_at >= self.end_timestamp {
-            return Err(VotingError::VotingEnded);
+        self.assert_voting_open(vote_at)?;
+        self.assert_not_ranked()?;
+        if self.is_secret_ballot() {
+            return Err(VotingError::WrongVoteMode);
         }
+        self.add_vote(public_key, project_id, vote)
+    }
+
+    fn add_vote(
+        &mut self,
+        public_key: PublicKey,
+        project_id: ProjectId,
+        vote: u64,
+    ) -> Result<(), VotingError> {
         match self.participants.get_mut(&public_key) {
             None => Err(VotingError::NotAParticipant),
             Some(participant) => {
-                let new_used_voting_power = participant.used_voting_power + vote;
-                if new_used_voting_power > participant.total_voting_power {
-                    Err(VotingError::NotEnoughVotingPower)
-                } else if !self.projects.contains_key(&project_id) {
-                    Err(VotingError::ProjectDoesNotExists)
+                if let VoteMode::Approval(_) = self.vote_mode {
+                    if vote != 1 {
+                        return Err(VotingError::InvalidApproval);
+                    }
+                }
+                if participant.team == Some(project_id) {
+                    return Err(VotingError::CannotVoteForOwnProject);
+                }
+                let previous_vote = participant.votes.get(&project_id).cloned().unwrap_or(0);
+                let new_vote = self.vote_mode.combine(previous_vote, vote);
+                let new_used_voting_power = (participant.used_voting_power
+                    - self.vote_mode.cost(previous_vote))
+                .saturating_add(self.vote_mode.cost(new_vote));
+                self.vote_mode.check_budget(
+                    participant.used_voting_power,
+                    new_used_voting_power,
+                    participant.voting_power(),
+                )?;
+                if !self.projects.contains_key(&project_id) {
+                    Err(VotingError::ProjectDoesNotExists { project_id })
                 } else {
-                    (*participant).votes.insert(project_id, vote);
+                    (*participant).votes.insert(project_id, new_vote);
                     (*participant).used_voting_power = new_used_voting_power;
                     Ok(())
                 }
@@ -136,118 +561,630 @@ impl Voting {
         }
     }
 
-    pub fn serialize(&self) -> VotingSerialized {
-        (
-            (self.start_timestamp, self.end_timestamp),
-            self.serialize_projects(),
-            self.serialize_participants(),
-        )
+    pub fn retract_vote(
+        &mut self,
+        public_key: PublicKey,
+        project_id: ProjectId,
+        vote_at: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_voting_open(vote_at)?;
+        self.assert_not_ranked()?;
+        match self.participants.get_mut(&public_key) {
+            None => Err(VotingError::NotAParticipant),
+            Some(participant) => match participant.votes.remove(&project_id) {
+                None => Err(VotingError::VoteDoesNotExist),
+                Some(vote) => {
+                    participant.used_voting_power -= self.vote_mode.cost(vote);
+                    Ok(())
+                }
+            },
+        }
     }
 
-    fn serialize_projects(&self) -> ProjectsSerialized {
-        let mut output = BTreeMap::new();
-        for (key, project) in self.projects.iter() {
-            output.insert(
-                key.0,
-                [
-                    project.name.clone(),
-                    project.team_name.clone(),
-                    project.video_link.clone(),
-                    project.github_link.clone(),
-                    project.google_drive_link.clone(),
-                ],
-            );
+    pub fn change_vote(
+        &mut self,
+        public_key: PublicKey,
+        from_project_id: ProjectId,
+        to_project_id: ProjectId,
+        vote_at: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_voting_open(vote_at)?;
+        self.assert_not_ranked()?;
+        match self.participants.get_mut(&public_key) {
+            None => Err(VotingError::NotAParticipant),
+            Some(participant) => {
+                if !participant.votes.contains_key(&from_project_id) {
+                    Err(VotingError::VoteDoesNotExist)
+                } else if !self.projects.contains_key(&to_project_id) {
+                    Err(VotingError::ProjectDoesNotExists {
+                        project_id: to_project_id,
+                    })
+                } else if participant.team == Some(to_project_id) {
+                    Err(VotingError::CannotVoteForOwnProject)
+                } else if from_project_id == to_project_id {
+                    Ok(())
+                } else {
+                    let from_vote = participant
+                        .votes
+                        .get(&from_project_id)
+                        .cloned()
+                        .unwrap_or(0);
+                    let to_vote = participant.votes.get(&to_project_id).cloned().unwrap_or(0);
+                    let new_vote = self.vote_mode.combine(to_vote, from_vote);
+                    let new_used_voting_power = (participant.used_voting_power
+                        - self.vote_mode.cost(from_vote)
+                        - self.vote_mode.cost(to_vote))
+                    .saturating_add(self.vote_mode.cost(new_vote));
+                    self.vote_mode.check_budget(
+                        participant.used_voting_power,
+                        new_used_voting_power,
+                        participant.voting_power(),
+                    )?;
+                    participant.votes.remove(&from_project_id);
+                    participant.votes.insert(to_project_id, new_vote);
+                    participant.used_voting_power = new_used_voting_power;
+                    Ok(())
+                }
+            }
         }
-        output
     }
 
-    fn serialize_participants(&self) -> ParticipantsSerialized {
-        let mut output = BTreeMap::new();
-        for (key, participant) in self.participants.iter() {
-            let mut votes = BTreeMap::new();
-            for (project_id, vote) in participant.votes.iter() {
-                votes.insert(project_id.0, *vote);
+    pub fn commit_vote(
+        &mut self,
+        public_key: PublicKey,
+        commitment: Commitment,
+        vote_at: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_voting_open(vote_at)?;
+        self.assert_not_ranked()?;
+        if !self.is_secret_ballot() {
+            return Err(VotingError::WrongVoteMode);
+        }
+        match self.participants.get_mut(&public_key) {
+            None => Err(VotingError::NotAParticipant),
+            // A new commitment replaces the previous one, so only one vote
+            // can be revealed after the standings of others are known.
+            Some(participant) => {
+                participant.commitment = Some(commitment);
+                Ok(())
             }
-            output.insert(
-                key.value(),
-                (
-                    participant.total_voting_power,
-                    participant.used_voting_power,
-                    votes,
-                ),
-            );
         }
-        output
     }
 
-    pub fn deserialize(value: VotingSerialized) -> Voting {
-        Voting {
-            start_timestamp: (value.0).0,
-            end_timestamp: (value.0).1,
-            projects: Voting::deserialize_projects(value.1),
-            participants: Voting::deserialize_participants(value.2),
+    pub fn reveal_vote(
+        &mut self,
+        public_key: PublicKey,
+        project_id: ProjectId,
+        vote: u64,
+        salt: Salt,
+        reveal_at: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        let reveal_end_timestamp = match self.reveal_end_timestamp {
+            None => return Err(VotingError::WrongVoteMode),
+            Some(reveal_end_timestamp) => reveal_end_timestamp,
+        };
+        if reveal_at < self.end_timestamp {
+            return Err(VotingError::RevealNotStarted);
+        }
+        if reveal_at >= reveal_end_timestamp {
+            return Err(VotingError::RevealEnded);
         }
+        let commitment = vote_commitment(project_id, vote, salt);
+        match self.participants.get(&public_key) {
+            None => return Err(VotingError::NotAParticipant),
+            Some(participant) if participant.commitment != Some(commitment) => {
+                return Err(VotingError::CommitmentDoesNotMatch)
+            }
+            Some(_) => {}
+        }
+        self.add_vote(public_key, project_id, vote)?;
+        if let Some(participant) = self.participants.get_mut(&public_key) {
+            participant.commitment = None;
+        }
+        Ok(())
     }
 
-    fn deserialize_projects(value: ProjectsSerialized) -> BTreeMap<ProjectId, Project> {
-        let mut output = BTreeMap::new();
-        for (id, list) in value.iter() {
-            output.insert(
-                ProjectId(*id),
-                Project {
-                    name: list[0].clone(),
-                    team_name: list[1].clone(),
-                    video_link: list[2].clone(),
-                    github_link: list[3].clone(),
-                    google_drive_link: list[4].clone(),
-                },
-            );
+    pub fn cast_ranked_vote(
+        &mut self,
+        public_key: PublicKey,
+        ranking: Vec<ProjectId>,
+        vote_at: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_voting_open(vote_at)?;
+        if self.vote_mode != VoteMode::Ranked || self.is_secret_ballot() {
+            return Err(VotingError::WrongVoteMode);
+        }
+        match self.participants.get_mut(&public_key) {
+            None => Err(VotingError::NotAParticipant),
+            Some(participant) => {
+                let mut ranked = BTreeSet::new();
+                for project_id in ranking.iter() {
+                    if !self.projects.contains_key(project_id) {
+                        return Err(VotingError::ProjectDoesNotExists {
+                            project_id: *project_id,
+                        });
+                    }
+                    if !ranked.insert(*project_id) {
+                        return Err(VotingError::DuplicatedProject);
+                    }
+                    if participant.team == Some(*project_id) {
+                        return Err(VotingError::CannotVoteForOwnProject);
+                    }
+                }
+                participant.ranking = ranking;
+                Ok(())
+            }
         }
-        output
     }
 
-    fn deserialize_participants(value: ParticipantsSerialized) -> BTreeMap<PublicKey, Participant> {
-        let mut output = BTreeMap::new();
-        for (public_key, (total_voting_power, used_voting_power, votes)) in value.iter() {
-            let mut output_votes = BTreeMap::new();
-            for (project_id, vote) in votes {
-                output_votes.insert(ProjectId(*project_id), *vote);
-            }
-            output.insert(
-                PublicKey::ed25519_from(*public_key),
-                Participant {
-                    total_voting_power: *total_voting_power,
-                    used_voting_power: *used_voting_power,
-                    votes: output_votes,
-                },
-            );
+    pub fn delegate_voting_power(
+        &mut self,
+        public_key: PublicKey,
+        delegate: PublicKey,
+        voting_power: u64,
+        delegate_at: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_delegation_open(delegate_at)?;
+        if !self.participants.contains_key(&public_key)
+            || !self.participants.contains_key(&delegate)
+        {
+            return Err(VotingError::NotAParticipant);
         }
-        output
+        if self.is_delegating_to(delegate, public_key) {
+            return Err(VotingError::DelegationCycle);
+        }
+        if let Some(participant) = self.participants.get_mut(&public_key) {
+            let free_voting_power = participant.remaining_voting_power();
+            if voting_power > free_voting_power {
+                return Err(VotingError::NotEnoughVotingPower {
+                    required: voting_power,
+                    available: free_voting_power,
+                });
+            }
+            let delegated = participant.delegations.entry(delegate).or_insert(0);
+            *delegated = delegated.saturating_add(voting_power);
+        }
+        if let Some(participant) = self.participants.get_mut(&delegate) {
+            participant.received_voting_power = participant
+                .received_voting_power
+                .saturating_add(voting_power);
+        }
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloc::string::ToString;
-
-    pub const ALI: PublicKey = PublicKey::ed25519_from([1u8; 32]);
-    pub const BOB: PublicKey = PublicKey::ed25519_from([2u8; 32]);
-    pub const JON: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+    pub fn revoke_delegation(
+        &mut self,
+        public_key: PublicKey,
+        delegate: PublicKey,
+        revoke_at: u64,
+    ) -> Result<(), VotingError> {
+        self.assert_delegation_open(revoke_at)?;
+        match self.participants.get_mut(&public_key) {
+            None => Err(VotingError::NotAParticipant),
+            Some(participant) => match participant.delegations.remove(&delegate) {
+                None => Err(VotingError::DelegationDoesNotExist),
+                Some(voting_power) => {
+                    self.release_voting_power(delegate, voting_power);
+                    Ok(())
+                }
+            },
+        }
+    }
 
-    fn example_project(name: &str) -> Project {
-        Project {
-            name: name.to_string(),
-            team_name: "casperlabs".to_string(),
-            video_link: "https://www.youtube.com/channel/UCjFz9Sfi4yFwocnDQTWDSqA".to_string(),
-            github_link: "https://github.com/CasperLabs/CasperLabs".to_string(),
-            google_drive_link: "http://drive.google.com".to_string(),
+    // Takes back delegated power from the participant. If that leaves the
+    // participant with less power than it has passed on or spent, its own
+    // delegations are revoked and then its votes are cancelled.
+    fn release_voting_power(&mut self, public_key: PublicKey, voting_power: u64) {
+        let delegations = match self.participants.get_mut(&public_key) {
+            None => return,
+            Some(participant) => {
+                participant.received_voting_power = participant
+                    .received_voting_power
+                    .saturating_sub(voting_power);
+                let available_voting_power = participant
+                    .total_voting_power
+                    .saturating_add(participant.received_voting_power);
+                if available_voting_power < participant.delegated_voting_power() {
+                    mem::take(&mut participant.delegations)
+                } else {
+                    BTreeMap::new()
+                }
+            }
+        };
+        for (delegate, voting_power) in delegations {
+            self.release_voting_power(delegate, voting_power);
+        }
+        if let Some(participant) = self.participants.get_mut(&public_key) {
+            if participant.used_voting_power > participant.voting_power() {
+                participant.used_voting_power = 0;
+                participant.votes = BTreeMap::new();
+            }
         }
     }
 
-    #[test]
-    fn test_init() {
-        let start = 10;
+    // Follows the delegation chain starting at `from`.
+    fn is_delegating_to(&self, from: PublicKey, to: PublicKey) -> bool {
+        let mut visited = BTreeSet::new();
+        let mut queue = vec![from];
+        while let Some(public_key) = queue.pop() {
+            if public_key == to {
+                return true;
+            }
+            if !visited.insert(public_key) {
+                continue;
+            }
+            if let Some(participant) = self.participants.get(&public_key) {
+                queue.extend(participant.delegations.keys().cloned());
+            }
+        }
+        false
+    }
+
+    // Delegation moves voting power, so it makes no sense in modes that
+    // do not use it. It is possible until the end of the voting.
+    fn assert_delegation_open(&self, delegate_at: u64) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        match self.vote_mode {
+            VoteMode::Ranked | VoteMode::Approval(_) => Err(VotingError::WrongVoteMode),
+            _ if delegate_at >= self.end_timestamp => Err(VotingError::VotingEnded),
+            _ => Ok(()),
+        }
+    }
+
+    fn assert_not_ranked(&self) -> Result<(), VotingError> {
+        if self.vote_mode == VoteMode::Ranked {
+            Err(VotingError::WrongVoteMode)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn assert_voting_open(&self, vote_at: u64) -> Result<(), VotingError> {
+        self.assert_not_finalized()?;
+        if vote_at < self.start_timestamp {
+            Err(VotingError::VotingNotStarted)
+        } else if vote_at >= self.end_timestamp {
+            Err(VotingError::VotingEnded)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn check_invariants(&self) -> Result<(), VotingError> {
+        let mut received_voting_power: BTreeMap<PublicKey, u64> = BTreeMap::new();
+        for (public_key, participant) in self.participants.iter() {
+            for (delegate, voting_power) in participant.delegations.iter() {
+                if !self.participants.contains_key(delegate)
+                    || self.is_delegating_to(*delegate, *public_key)
+                {
+                    return Err(VotingError::InvariantViolated);
+                }
+                let received = received_voting_power.entry(*delegate).or_insert(0);
+                *received = received
+                    .checked_add(*voting_power)
+                    .ok_or(VotingError::InvariantViolated)?;
+            }
+        }
+        for (public_key, participant) in self.participants.iter() {
+            let received = received_voting_power.get(public_key).cloned().unwrap_or(0);
+            if received != participant.received_voting_power {
+                return Err(VotingError::InvariantViolated);
+            }
+            self.check_participant_invariants(public_key)?;
+        }
+        Ok(())
+    }
+
+    // Checks that don't need other participants, so they can be done
+    // when only one participant is loaded.
+    pub fn check_participant_invariants(&self, public_key: &PublicKey) -> Result<(), VotingError> {
+        if let Some(participant) = self.participants.get(public_key) {
+            if participant
+                .total_voting_power
+                .saturating_add(participant.received_voting_power)
+                < participant.delegated_voting_power()
+            {
+                return Err(VotingError::InvariantViolated);
+            }
+            let mut used_voting_power: u64 = 0;
+            for (project_id, vote) in participant.votes.iter() {
+                if !self.projects.contains_key(project_id) {
+                    return Err(VotingError::InvariantViolated);
+                }
+                if let VoteMode::Approval(_) = self.vote_mode {
+                    if *vote != 1 {
+                        return Err(VotingError::InvariantViolated);
+                    }
+                }
+                used_voting_power = used_voting_power
+                    .checked_add(self.vote_mode.cost(*vote))
+                    .ok_or(VotingError::InvariantViolated)?;
+            }
+            if used_voting_power != participant.used_voting_power
+                || self
+                    .vote_mode
+                    .check_budget(0, used_voting_power, participant.voting_power())
+                    .is_err()
+            {
+                return Err(VotingError::InvariantViolated);
+            }
+            let mut ranked = BTreeSet::new();
+            for project_id in participant.ranking.iter() {
+                if !self.projects.contains_key(project_id) || !ranked.insert(project_id) {
+                    return Err(VotingError::InvariantViolated);
+                }
+            }
+            if let Some(project_id) = participant.team {
+                if !self.projects.contains_key(&project_id)
+                    || participant.votes.contains_key(&project_id)
+                    || participant.ranking.contains(&project_id)
+                {
+                    return Err(VotingError::InvariantViolated);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn tally(&self) -> BTreeMap<ProjectId, u64> {
+        let mut output: BTreeMap<ProjectId, u64> = BTreeMap::new();
+        for project_id in self.projects.keys() {
+            output.insert(*project_id, 0);
+        }
+        for participant in self.participants.values() {
+            for (project_id, vote) in participant.votes.iter() {
+                if let Some(total) = output.get_mut(project_id) {
+                    *total = total.saturating_add(*vote);
+                }
+            }
+        }
+        output
+    }
+
+    pub fn instant_runoff(&self) -> Vec<(ProjectId, u64)> {
+        let candidates = self.projects.keys().cloned().collect();
+        let ballots: Vec<&Vec<ProjectId>> = self
+            .participants
+            .values()
+            .map(|participant| &participant.ranking)
+            .collect();
+        instant_runoff(candidates, &ballots)
+    }
+
+    pub fn leaderboard(&self) -> Vec<(ProjectId, u64)> {
+        if self.vote_mode == VoteMode::Ranked {
+            return self.instant_runoff();
+        }
+        let mut output: Vec<(ProjectId, u64)> = self.tally().into_iter().collect();
+        // Most votes first, ties resolved by the lower project id.
+        output.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        output
+    }
+
+    pub fn remaining_voting_power(&self, public_key: &PublicKey) -> Result<u64, VotingError> {
+        self.participants
+            .get(public_key)
+            .map(Participant::remaining_voting_power)
+            .ok_or(VotingError::NotAParticipant)
+    }
+
+    pub fn results(&self, now: u64) -> Result<Vec<(ProjectId, u64)>, VotingError> {
+        if now < self.closed_at() {
+            Err(VotingError::VotingNotEnded)
+        } else {
+            Ok(self.leaderboard())
+        }
+    }
+
+    pub fn serialize(&self) -> VotingSerialized {
+        (
+            VERSION,
+            (
+                (
+                    self.start_timestamp,
+                    self.end_timestamp,
+                    self.reveal_end_timestamp,
+                ),
+                self.finalized,
+                Voting::serialize_vote_mode(self.vote_mode),
+            ),
+            Voting::serialize_projects(&self.projects),
+            self.serialize_participants(),
+        )
+    }
+
+    fn serialize_vote_mode(vote_mode: VoteMode) -> (u8, u64) {
+        match vote_mode {
+            VoteMode::Accumulate => (0, 0),
+            VoteMode::Replace => (1, 0),
+            VoteMode::Quadratic => (2, 0),
+            VoteMode::Ranked => (3, 0),
+            VoteMode::Approval(limit) => (4, limit),
+        }
+    }
+
+    pub fn serialize_projects(projects: &BTreeMap<ProjectId, Project>) -> ProjectsSerialized {
+        projects
+            .iter()
+            .map(|(key, project)| (key.0, Voting::serialize_project(project)))
+            .collect()
+    }
+
+    pub fn serialize_project(project: &Project) -> ProjectSerialized {
+        let mut fields = project.attributes.clone();
+        fields.insert(String::from(NAME), project.name.clone());
+        fields.insert(String::from(TEAM), project.team_name.clone());
+        fields
+    }
+
+    fn serialize_participants(&self) -> ParticipantsSerialized {
+        self.participants
+            .iter()
+            .map(|(key, participant)| (key.value(), Voting::serialize_participant(participant)))
+            .collect()
+    }
+
+    pub fn serialize_participant(participant: &Participant) -> ParticipantSerialized {
+        let mut votes = BTreeMap::new();
+        for (project_id, vote) in participant.votes.iter() {
+            votes.insert(project_id.0, *vote);
+        }
+        let ranking = participant
+            .ranking
+            .iter()
+            .map(|project_id| project_id.0)
+            .collect();
+        let mut delegations = BTreeMap::new();
+        for (delegate, voting_power) in participant.delegations.iter() {
+            delegations.insert(delegate.value(), *voting_power);
+        }
+        (
+            (
+                (
+                    participant.total_voting_power,
+                    participant.used_voting_power,
+                    participant.received_voting_power,
+                ),
+                participant.team.map(|project_id| project_id.0),
+            ),
+            votes,
+            (ranking, participant.commitment, delegations),
+        )
+    }
+
+    pub fn serialize_results(results: &[(ProjectId, u64)]) -> ResultsSerialized {
+        results
+            .iter()
+            .map(|(project_id, votes)| (project_id.0, *votes))
+            .collect()
+    }
+
+    // Reads the current layout only.
+    pub fn deserialize(value: VotingSerialized) -> Result<Voting, VotingError> {
+        if value.0 != VERSION {
+            return Err(VotingError::UnsupportedVersion);
+        }
+        Ok(Voting {
+            start_timestamp: ((value.1).0).0,
+            end_timestamp: ((value.1).0).1,
+            reveal_end_timestamp: ((value.1).0).2,
+            finalized: (value.1).1,
+            vote_mode: Voting::deserialize_vote_mode((value.1).2)?,
+            projects: Voting::deserialize_projects(value.2),
+            participants: Voting::deserialize_participants(value.3),
+        })
+    }
+
+    // Tags of vote modes added by newer versions are not read as any known mode.
+    fn deserialize_vote_mode(value: (u8, u64)) -> Result<VoteMode, VotingError> {
+        match value {
+            (0, _) => Ok(VoteMode::Accumulate),
+            (1, _) => Ok(VoteMode::Replace),
+            (2, _) => Ok(VoteMode::Quadratic),
+            (3, _) => Ok(VoteMode::Ranked),
+            (4, limit) => Ok(VoteMode::Approval(limit)),
+            _ => Err(VotingError::UnsupportedVersion),
+        }
+    }
+
+    pub fn deserialize_projects(value: ProjectsSerialized) -> BTreeMap<ProjectId, Project> {
+        value
+            .into_iter()
+            .map(|(id, fields)| (ProjectId(id), Voting::deserialize_project(fields)))
+            .collect()
+    }
+
+    pub fn deserialize_project(mut fields: ProjectSerialized) -> Project {
+        Project {
+            name: fields.remove(NAME).unwrap_or_default(),
+            team_name: fields.remove(TEAM).unwrap_or_default(),
+            attributes: fields,
+        }
+    }
+
+    // `PublicKey` of this version of `casperlabs-types` is the 32 bytes
+    // identifying the account and doesn't carry the key algorithm, so
+    // `ed25519_from` rebuilds the key of any account as it was.
+    fn deserialize_participants(value: ParticipantsSerialized) -> BTreeMap<PublicKey, Participant> {
+        value
+            .into_iter()
+            .map(|(public_key, participant)| {
+                (
+                    PublicKey::ed25519_from(public_key),
+                    Voting::deserialize_participant(participant),
+                )
+            })
+            .collect()
+    }
+
+    pub fn deserialize_participant(value: ParticipantSerialized) -> Participant {
+        let (
+            ((total_voting_power, used_voting_power, received_voting_power), team),
+            votes,
+            (ranking, commitment, delegations),
+        ) = value;
+        Participant {
+            total_voting_power,
+            used_voting_power,
+            votes: votes
+                .into_iter()
+                .map(|(project_id, vote)| (ProjectId(project_id), vote))
+                .collect(),
+            ranking: ranking.into_iter().map(ProjectId).collect(),
+            commitment,
+            delegations: delegations
+                .into_iter()
+                .map(|(delegate, voting_power)| (PublicKey::ed25519_from(delegate), voting_power))
+                .collect(),
+            received_voting_power,
+            team: team.map(ProjectId),
+        }
+    }
+
+    pub fn deserialize_results(value: ResultsSerialized) -> Vec<(ProjectId, u64)> {
+        value
+            .into_iter()
+            .map(|(project_id, votes)| (ProjectId(project_id), votes))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    pub const ALI: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+    pub const BOB: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+    pub const JON: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+
+    fn example_project(name: &str) -> Project {
+        let mut attributes = BTreeMap::new();
+        attributes.insert(
+            VIDEO.to_string(),
+            "https://www.youtube.com/channel/UCjFz9Sfi4yFwocnDQTWDSqA".to_string(),
+        );
+        attributes.insert(
+            GITHUB.to_string(),
+            "https://github.com/CasperLabs/CasperLabs".to_string(),
+        );
+        attributes.insert(
+            GOOGLE_DRIVE.to_string(),
+            "http://drive.google.com".to_string(),
+        );
+        Project {
+            name: name.to_string(),
+            team_name: "casperlabs".to_string(),
+            attributes,
+        }
+    }
+
+    #[test]
+    fn test_init() {
+        let start = 10;
         assert!(Voting::new(start, start).is_err());
         assert!(Voting::new(start, start - 1).is_err());
         let voting = Voting::new(start, start + 1).unwrap();
@@ -255,41 +1192,114 @@ mod tests {
         assert_eq!(voting.end_at(), start + 1);
     }
 
+    #[test]
+    fn test_set_voting_window() {
+        let mut voting = Voting::new(1, 3).unwrap();
+        assert_eq!(
+            voting.set_voting_window(5, 5, 0).unwrap_err(),
+            VotingError::StartNotBeforeEnd
+        );
+
+        // Window can be moved freely before anybody votes.
+        voting.set_voting_window(0, 2, 4).unwrap();
+        assert_eq!(voting.start_at(), 0);
+        assert_eq!(voting.end_at(), 2);
+
+        voting.set_voting_window(1, 3, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, project_id, 2, 2).unwrap();
+
+        // Votes cannot be closed retroactively.
+        assert_eq!(
+            voting.set_voting_window(1, 2, 2).unwrap_err(),
+            VotingError::EndInThePast
+        );
+        voting.set_voting_window(1, 5, 2).unwrap();
+        assert_eq!(voting.end_at(), 5);
+
+        // Reveal phase has to stay after the voting.
+        voting.enable_secret_ballot(6).unwrap();
+        assert_eq!(
+            voting.set_voting_window(1, 6, 2).unwrap_err(),
+            VotingError::RevealEndNotAfterEnd
+        );
+
+        voting.finalize(6).unwrap();
+        assert_eq!(
+            voting.set_voting_window(1, 7, 6).unwrap_err(),
+            VotingError::VotingFinalized
+        );
+    }
+
     #[test]
     fn test_add_or_update_participant() {
         let mut voting = Voting::new(1, 2).unwrap();
         let ali_power = 10;
 
         // Add participant.
-        voting.add_or_update_participant(ALI, ali_power);
+        voting.add_or_update_participant(ALI, ali_power).unwrap();
         assert_eq!(
             voting.participants.get(&ALI).unwrap(),
             &Participant {
                 total_voting_power: ali_power,
                 used_voting_power: 0,
-                votes: BTreeMap::new()
+                votes: BTreeMap::new(),
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
             }
         );
 
         // Update particpant.
         let updated_ali_power = 11;
-        voting.add_or_update_participant(ALI, updated_ali_power);
+        voting
+            .add_or_update_participant(ALI, updated_ali_power)
+            .unwrap();
         assert_eq!(
             voting.participants.get(&ALI).unwrap(),
             &Participant {
                 total_voting_power: updated_ali_power,
                 used_voting_power: 0,
-                votes: BTreeMap::new()
+                votes: BTreeMap::new(),
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
             }
         );
     }
 
+    #[test]
+    fn test_add_or_update_participants() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting
+            .add_or_update_participants(vec![(ALI, 10), (BOB, 20)])
+            .unwrap();
+        assert_eq!(voting.participants.len(), 2);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap().total_voting_power,
+            10
+        );
+        assert_eq!(
+            voting.participants.get(&BOB).unwrap().total_voting_power,
+            20
+        );
+    }
+
     #[test]
     fn test_remove_participant() {
         let mut voting = Voting::new(1, 2).unwrap();
         let ali_power = 10;
-        voting.add_or_update_participant(ALI, ali_power);
-        voting.remove_participant_if_exists(&ALI);
+        voting.add_or_update_participant(ALI, ali_power).unwrap();
+        voting.remove_participant_if_exists(&ALI).unwrap();
         assert!(voting.participants.get(&ALI).is_none());
     }
 
@@ -300,88 +1310,308 @@ mod tests {
         let project = example_project("project");
 
         // Add new project.
-        voting.add_or_update_project(project_id, project.clone());
+        voting
+            .add_or_update_project(project_id, project.clone())
+            .unwrap();
         assert_eq!(voting.projects.get(&project_id).unwrap(), &project);
 
         // Update the project.
         let updated_project = example_project("project2");
-        voting.add_or_update_project(project_id, updated_project.clone());
+        voting
+            .add_or_update_project(project_id, updated_project.clone())
+            .unwrap();
         assert_eq!(voting.projects.get(&project_id).unwrap(), &updated_project);
     }
 
     #[test]
-    fn test_remove_project() {
+    fn test_project_attributes() {
         let mut voting = Voting::new(1, 2).unwrap();
         let project_id = ProjectId(1);
-        let project = example_project("project");
-        voting.add_or_update_project(project_id, project);
-        voting.remove_project_if_exists_and_cancel_votes(project_id);
-        assert!(voting.projects.get(&project_id).is_none());
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+
+        let track = || String::from("track");
+        voting
+            .set_project_attribute(project_id, track(), String::from("defi"))
+            .unwrap();
+        assert_eq!(voting.projects[&project_id].attributes[&track()], "defi");
+        voting
+            .set_project_attribute(project_id, track(), String::from("games"))
+            .unwrap();
+        assert_eq!(voting.projects[&project_id].attributes[&track()], "games");
+
+        voting
+            .delete_project_attribute(project_id, "track")
+            .unwrap();
+        assert!(!voting.projects[&project_id]
+            .attributes
+            .contains_key(&track()));
+        assert_eq!(
+            voting
+                .delete_project_attribute(project_id, "track")
+                .unwrap_err(),
+            VotingError::AttributeDoesNotExist
+        );
+        assert_eq!(
+            voting
+                .set_project_attribute(ProjectId(2), track(), String::from("defi"))
+                .unwrap_err(),
+            VotingError::ProjectDoesNotExists {
+                project_id: ProjectId(2)
+            }
+        );
     }
 
     #[test]
-    fn test_voting() {
+    fn test_reserved_project_attributes() {
         let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        let mut project = example_project("project");
+        project
+            .attributes
+            .insert(String::from(NAME), String::from("other"));
+        assert_eq!(
+            voting
+                .add_or_update_project(project_id, project)
+                .unwrap_err(),
+            VotingError::ReservedAttribute
+        );
 
-        // Setup projects.
-        let a_project_id = ProjectId(1);
-        let a_project = example_project("a_project");
-        let b_project_id = ProjectId(2);
-        let b_project = example_project("b_project");
-        voting.add_or_update_project(a_project_id, a_project);
-        voting.add_or_update_project(b_project_id, b_project);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        assert_eq!(
+            voting
+                .set_project_attribute(project_id, String::from(TEAM), String::from("other"))
+                .unwrap_err(),
+            VotingError::ReservedAttribute
+        );
+    }
 
-        // Setup participants.
-        let ali_power = 3;
-        let bob_power = 5;
-        voting.add_or_update_participant(ALI, ali_power);
-        voting.add_or_update_participant(BOB, bob_power);
+    #[test]
+    fn test_project_validation() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        let long = "a".repeat(MAX_FIELD_LENGTH + 1);
 
-        // Cannot vote before voting starts.
-        let vote_at = 0;
+        let mut project = example_project("");
         assert_eq!(
-            voting.cast_vote(ALI, a_project_id, 1, vote_at).unwrap_err(),
-            VotingError::VotingNotStarted
+            voting
+                .add_or_update_project(project_id, project.clone())
+                .unwrap_err(),
+            VotingError::EmptyProjectName
         );
 
-        // Cannot vote after voting ends.
-        let vote_at = 2;
+        project.name = long.clone();
         assert_eq!(
-            voting.cast_vote(ALI, a_project_id, 1, vote_at).unwrap_err(),
-            VotingError::VotingEnded
+            voting
+                .add_or_update_project(project_id, project.clone())
+                .unwrap_err(),
+            VotingError::FieldTooLong {
+                length: MAX_FIELD_LENGTH + 1
+            }
         );
 
-        // Cannot vote more then voting power.
-        let vote_at = 1;
+        project.name = String::from("project");
+        project.attributes.insert(String::from("track"), long);
         assert_eq!(
             voting
-                .cast_vote(ALI, b_project_id, ali_power + 1, vote_at)
+                .add_or_update_project(project_id, project.clone())
                 .unwrap_err(),
-            VotingError::NotEnoughVotingPower
+            VotingError::FieldTooLong {
+                length: MAX_FIELD_LENGTH + 1
+            }
         );
 
-        // Cannot vote on non exisitng projects.
-        let unknown_project_id = ProjectId(3);
+        project.attributes.remove("track");
+        project
+            .attributes
+            .insert(String::new(), String::from("value"));
         assert_eq!(
             voting
-                .cast_vote(ALI, unknown_project_id, ali_power, vote_at)
+                .add_or_update_project(project_id, project.clone())
                 .unwrap_err(),
-            VotingError::ProjectDoesNotExists
+            VotingError::EmptyAttributeName
         );
 
-        // Cannot vote as non participant.
+        project.attributes.remove("");
+        for i in 0..MAX_ATTRIBUTES {
+            project
+                .attributes
+                .insert(format!("attribute_{}", i), String::from("value"));
+        }
         assert_eq!(
             voting
-                .cast_vote(JON, a_project_id, ali_power, vote_at)
+                .add_or_update_project(project_id, project.clone())
                 .unwrap_err(),
-            VotingError::NotAParticipant
+            VotingError::TooManyAttributes
         );
 
-        // Vote as Ali.
-        let ali_a_project_vote = 2;
-        let ali_b_project_vote = 1;
+        project = example_project("project");
+        project
+            .attributes
+            .insert(String::from(GITHUB), String::from("github.com/casperlabs"));
+        assert_eq!(
+            voting
+                .add_or_update_project(project_id, project)
+                .unwrap_err(),
+            VotingError::InvalidLink
+        );
+        assert!(voting.projects.is_empty());
+    }
+
+    #[test]
+    fn test_project_attribute_validation() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
         voting
-            .cast_vote(ALI, a_project_id, ali_a_project_vote, vote_at)
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        assert_eq!(
+            voting
+                .set_project_attribute(project_id, String::from(VIDEO), String::from("https:// x"))
+                .unwrap_err(),
+            VotingError::InvalidLink
+        );
+        voting
+            .set_project_attribute(
+                project_id,
+                String::from(VIDEO),
+                String::from("http://example.com/video"),
+            )
+            .unwrap();
+        // Other attributes don't have to be links.
+        voting
+            .set_project_attribute(project_id, String::from("track"), String::from("defi"))
+            .unwrap();
+        assert_eq!(
+            voting
+                .set_project_attribute(project_id, String::new(), String::from("value"))
+                .unwrap_err(),
+            VotingError::EmptyAttributeName
+        );
+
+        let attributes = voting.projects[&project_id].attributes.len();
+        for i in attributes..MAX_ATTRIBUTES {
+            voting
+                .set_project_attribute(
+                    project_id,
+                    format!("attribute_{}", i),
+                    String::from("value"),
+                )
+                .unwrap();
+        }
+        assert_eq!(
+            voting
+                .set_project_attribute(project_id, String::from("demo"), String::from("value"))
+                .unwrap_err(),
+            VotingError::TooManyAttributes
+        );
+        // Existing attributes can still be changed.
+        voting
+            .set_project_attribute(project_id, String::from("track"), String::from("nft"))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_add_or_update_projects() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let mut projects = BTreeMap::new();
+        projects.insert(ProjectId(1), example_project("a_project"));
+        projects.insert(ProjectId(2), example_project("b_project"));
+        voting.add_or_update_projects(projects.clone()).unwrap();
+        assert_eq!(voting.projects, projects);
+
+        // Projects survive the encoding used by the contract arguments.
+        let serialized = Voting::serialize_projects(&projects);
+        assert_eq!(Voting::deserialize_projects(serialized), projects);
+    }
+
+    #[test]
+    fn test_remove_project() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        let project = example_project("project");
+        voting.add_or_update_project(project_id, project).unwrap();
+        voting
+            .remove_project_if_exists_and_cancel_votes(project_id)
+            .unwrap();
+        assert!(voting.projects.get(&project_id).is_none());
+    }
+
+    #[test]
+    fn test_voting() {
+        let mut voting = Voting::new(1, 2).unwrap();
+
+        // Setup projects.
+        let a_project_id = ProjectId(1);
+        let a_project = example_project("a_project");
+        let b_project_id = ProjectId(2);
+        let b_project = example_project("b_project");
+        voting
+            .add_or_update_project(a_project_id, a_project)
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, b_project)
+            .unwrap();
+
+        // Setup participants.
+        let ali_power = 3;
+        let bob_power = 5;
+        voting.add_or_update_participant(ALI, ali_power).unwrap();
+        voting.add_or_update_participant(BOB, bob_power).unwrap();
+
+        // Cannot vote before voting starts.
+        let vote_at = 0;
+        assert_eq!(
+            voting.cast_vote(ALI, a_project_id, 1, vote_at).unwrap_err(),
+            VotingError::VotingNotStarted
+        );
+
+        // Cannot vote after voting ends.
+        let vote_at = 2;
+        assert_eq!(
+            voting.cast_vote(ALI, a_project_id, 1, vote_at).unwrap_err(),
+            VotingError::VotingEnded
+        );
+
+        // Cannot vote more then voting power.
+        let vote_at = 1;
+        assert_eq!(
+            voting
+                .cast_vote(ALI, b_project_id, ali_power + 1, vote_at)
+                .unwrap_err(),
+            VotingError::NotEnoughVotingPower {
+                required: ali_power + 1,
+                available: ali_power
+            }
+        );
+
+        // Cannot vote on non exisitng projects.
+        let unknown_project_id = ProjectId(3);
+        assert_eq!(
+            voting
+                .cast_vote(ALI, unknown_project_id, ali_power, vote_at)
+                .unwrap_err(),
+            VotingError::ProjectDoesNotExists {
+                project_id: unknown_project_id
+            }
+        );
+
+        // Cannot vote as non participant.
+        assert_eq!(
+            voting
+                .cast_vote(JON, a_project_id, ali_power, vote_at)
+                .unwrap_err(),
+            VotingError::NotAParticipant
+        );
+
+        // Vote as Ali.
+        let ali_a_project_vote = 2;
+        let ali_b_project_vote = 1;
+        voting
+            .cast_vote(ALI, a_project_id, ali_a_project_vote, vote_at)
             .unwrap();
         voting
             .cast_vote(ALI, b_project_id, ali_b_project_vote, vote_at)
@@ -394,7 +1624,12 @@ mod tests {
             &Participant {
                 total_voting_power: ali_power,
                 used_voting_power: ali_a_project_vote + ali_b_project_vote,
-                votes: votes
+                votes: votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
             }
         );
 
@@ -415,12 +1650,19 @@ mod tests {
             &Participant {
                 total_voting_power: bob_power,
                 used_voting_power: bob_a_project_vote + bob_b_project_vote,
-                votes: votes
+                votes: votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
             }
         );
 
         // Removing project should remove votes.
-        voting.remove_project_if_exists_and_cancel_votes(b_project_id);
+        voting
+            .remove_project_if_exists_and_cancel_votes(b_project_id)
+            .unwrap();
         let mut votes = BTreeMap::new();
         votes.insert(a_project_id, ali_a_project_vote);
         assert_eq!(
@@ -428,7 +1670,12 @@ mod tests {
             &Participant {
                 total_voting_power: ali_power,
                 used_voting_power: ali_a_project_vote,
-                votes: votes
+                votes: votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
             }
         );
         let mut votes = BTreeMap::new();
@@ -438,35 +1685,1203 @@ mod tests {
             &Participant {
                 total_voting_power: bob_power,
                 used_voting_power: bob_a_project_vote,
-                votes: votes
+                votes: votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
             }
         );
 
         // Updating voting powert should remove votes.
         let updated_ali_power = 10;
-        voting.add_or_update_participant(ALI, updated_ali_power);
+        voting
+            .add_or_update_participant(ALI, updated_ali_power)
+            .unwrap();
         assert_eq!(
             voting.participants.get(&ALI).unwrap(),
             &Participant {
                 total_voting_power: updated_ali_power,
                 used_voting_power: 0,
-                votes: BTreeMap::new()
+                votes: BTreeMap::new(),
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
             }
         );
     }
 
     #[test]
-    fn test_serialization() {
+    fn test_voting_twice_on_the_same_project() {
+        let project_id = ProjectId(1);
+
+        // Accumulate adds the new vote to the previous one.
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Accumulate).unwrap();
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, project_id, 2, 1).unwrap();
+        voting.cast_vote(ALI, project_id, 3, 1).unwrap();
+        assert_eq!(
+            voting.cast_vote(ALI, project_id, 1, 1).unwrap_err(),
+            VotingError::NotEnoughVotingPower {
+                required: 1,
+                available: 0
+            }
+        );
+        let mut votes = BTreeMap::new();
+        votes.insert(project_id, 5);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 5,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+        voting.check_invariants().unwrap();
+
+        // Replace overrides the previous vote.
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Replace).unwrap();
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, project_id, 4, 1).unwrap();
+        voting.cast_vote(ALI, project_id, 5, 1).unwrap();
+        voting.cast_vote(ALI, project_id, 1, 1).unwrap();
+        let mut votes = BTreeMap::new();
+        votes.insert(project_id, 1);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 1,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+        voting.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn test_quadratic_voting() {
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Quadratic).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 10).unwrap();
+        voting.add_or_update_participant(BOB, 10).unwrap();
+
+        // Cannot put the whole budget on one project.
+        assert_eq!(
+            voting.cast_vote(ALI, a_project_id, 4, 1).unwrap_err(),
+            VotingError::NotEnoughVotingPower {
+                required: 16,
+                available: 10
+            }
+        );
+
+        // 2 votes cost 4, 1 more vote on the same project costs 5 more.
+        voting.cast_vote(ALI, a_project_id, 2, 1).unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().used_voting_power, 4);
+        voting.cast_vote(ALI, a_project_id, 1, 1).unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().used_voting_power, 9);
+        voting.cast_vote(ALI, b_project_id, 1, 1).unwrap();
+        let mut votes = BTreeMap::new();
+        votes.insert(a_project_id, 3);
+        votes.insert(b_project_id, 1);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 10,
+                used_voting_power: 10,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+
+        // Moving the vote has to be paid again.
+        assert_eq!(
+            voting
+                .change_vote(ALI, b_project_id, a_project_id, 1)
+                .unwrap_err(),
+            VotingError::NotEnoughVotingPower {
+                required: 6,
+                available: 0
+            }
+        );
+
+        // Tally counts votes, not the voting power.
+        voting.cast_vote(BOB, b_project_id, 3, 1).unwrap();
+        assert_eq!(
+            voting.leaderboard(),
+            vec![(b_project_id, 4), (a_project_id, 3)]
+        );
+        voting.check_invariants().unwrap();
+
+        // Retracting gives back the whole cost.
+        voting.retract_vote(ALI, a_project_id, 1).unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().used_voting_power, 1);
+        voting
+            .remove_project_if_exists_and_cancel_votes(b_project_id)
+            .unwrap();
+        assert_eq!(voting.participants.get(&BOB).unwrap().used_voting_power, 0);
+        voting.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn test_ranked_voting() {
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Ranked).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        let c_project_id = ProjectId(3);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(c_project_id, example_project("c_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 1).unwrap();
+        voting.add_or_update_participant(BOB, 1).unwrap();
+        voting.add_or_update_participant(JON, 1).unwrap();
+
+        // Power votes are not allowed.
+        assert_eq!(
+            voting.cast_vote(ALI, a_project_id, 1, 1).unwrap_err(),
+            VotingError::WrongVoteMode
+        );
+
+        // Ranking has to contain unique, existing projects.
+        assert_eq!(
+            voting
+                .cast_ranked_vote(ALI, vec![a_project_id, ProjectId(4)], 1)
+                .unwrap_err(),
+            VotingError::ProjectDoesNotExists {
+                project_id: ProjectId(4)
+            }
+        );
+        assert_eq!(
+            voting
+                .cast_ranked_vote(ALI, vec![a_project_id, a_project_id], 1)
+                .unwrap_err(),
+            VotingError::DuplicatedProject
+        );
+
+        // C is eliminated first and Jon's vote goes to B.
+        voting
+            .cast_ranked_vote(ALI, vec![a_project_id, b_project_id], 1)
+            .unwrap();
+        voting.cast_ranked_vote(BOB, vec![b_project_id], 1).unwrap();
+        voting
+            .cast_ranked_vote(JON, vec![c_project_id, b_project_id], 1)
+            .unwrap();
+        assert_eq!(
+            voting.participants.get(&JON).unwrap().ranking,
+            vec![c_project_id, b_project_id]
+        );
+        assert_eq!(
+            voting.leaderboard(),
+            vec![(b_project_id, 3), (a_project_id, 1), (c_project_id, 1)]
+        );
+        voting.check_invariants().unwrap();
+
+        // Removing the project removes it from rankings.
+        voting
+            .remove_project_if_exists_and_cancel_votes(b_project_id)
+            .unwrap();
+        assert_eq!(
+            voting.participants.get(&JON).unwrap().ranking,
+            vec![c_project_id]
+        );
+        assert_eq!(
+            voting.leaderboard(),
+            vec![(a_project_id, 1), (c_project_id, 1)]
+        );
+        voting.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn test_ranked_vote_in_power_mode() {
         let mut voting = Voting::new(1, 2).unwrap();
         let project_id = ProjectId(1);
-        let project = example_project("project");
-        let ali_power = 10;
-        voting.add_or_update_project(project_id, project);
-        voting.add_or_update_participant(ALI, ali_power);
-        voting.cast_vote(ALI, project_id, ali_power, 1).unwrap();
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 1).unwrap();
+        assert_eq!(
+            voting
+                .cast_ranked_vote(ALI, vec![project_id], 1)
+                .unwrap_err(),
+            VotingError::WrongVoteMode
+        );
+    }
 
-        let serialized = voting.serialize();
-        let deserialized = Voting::deserialize(serialized);
-        assert_eq!(voting, deserialized);
+    #[test]
+    fn test_approval_voting() {
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Approval(2)).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        let c_project_id = ProjectId(3);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(c_project_id, example_project("c_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 0).unwrap();
+        voting.add_or_update_participant(BOB, 0).unwrap();
+
+        // Every approval is exactly one vote.
+        assert_eq!(
+            voting.cast_vote(ALI, a_project_id, 2, 1).unwrap_err(),
+            VotingError::InvalidApproval
+        );
+
+        // Approving the same project twice counts once.
+        voting.cast_vote(ALI, a_project_id, 1, 1).unwrap();
+        voting.cast_vote(ALI, a_project_id, 1, 1).unwrap();
+        voting.cast_vote(ALI, b_project_id, 1, 1).unwrap();
+        assert_eq!(
+            voting.cast_vote(ALI, c_project_id, 1, 1).unwrap_err(),
+            VotingError::TooManyApprovals { limit: 2 }
+        );
+        let mut votes = BTreeMap::new();
+        votes.insert(a_project_id, 1);
+        votes.insert(b_project_id, 1);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 0,
+                used_voting_power: 2,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+
+        // Moving the approval to an already approved project drops it.
+        voting
+            .change_vote(ALI, a_project_id, b_project_id, 1)
+            .unwrap();
+        let mut votes = BTreeMap::new();
+        votes.insert(b_project_id, 1);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 0,
+                used_voting_power: 1,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+
+        voting.cast_vote(ALI, c_project_id, 1, 1).unwrap();
+        voting.cast_vote(BOB, c_project_id, 1, 1).unwrap();
+        assert_eq!(
+            voting.leaderboard(),
+            vec![(c_project_id, 2), (b_project_id, 1), (a_project_id, 0)]
+        );
+        voting.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn test_secret_ballot() {
+        let mut ranked = Voting::with_vote_mode(1, 2, VoteMode::Ranked).unwrap();
+        assert_eq!(
+            ranked.enable_secret_ballot(3).unwrap_err(),
+            VotingError::WrongVoteMode
+        );
+        let mut voting = Voting::new(1, 2).unwrap();
+        assert_eq!(
+            voting.enable_secret_ballot(2).unwrap_err(),
+            VotingError::RevealEndNotAfterEnd
+        );
+        voting.enable_secret_ballot(3).unwrap();
+        assert!(voting.is_secret_ballot());
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        let ali_salt = [1u8; 32];
+        let bob_salt = [2u8; 32];
+
+        // Votes cannot be casted openly.
+        assert_eq!(
+            voting.cast_vote(ALI, a_project_id, 1, 1).unwrap_err(),
+            VotingError::WrongVoteMode
+        );
+
+        // Commit during the voting.
+        let ali_commitment = vote_commitment(a_project_id, 3, ali_salt);
+        let bob_commitment = vote_commitment(b_project_id, 5, bob_salt);
+        voting.commit_vote(ALI, ali_commitment, 1).unwrap();
+        voting.commit_vote(BOB, bob_commitment, 1).unwrap();
+        assert_eq!(
+            voting.commit_vote(JON, ali_commitment, 1).unwrap_err(),
+            VotingError::NotAParticipant
+        );
+        assert_eq!(
+            voting.commit_vote(ALI, ali_commitment, 2).unwrap_err(),
+            VotingError::VotingEnded
+        );
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 0,
+                votes: BTreeMap::new(),
+                ranking: Vec::new(),
+                commitment: Some(ali_commitment),
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+
+        // Reveal after the voting.
+        assert_eq!(
+            voting
+                .reveal_vote(ALI, a_project_id, 3, ali_salt, 1)
+                .unwrap_err(),
+            VotingError::RevealNotStarted
+        );
+        assert_eq!(
+            voting
+                .reveal_vote(ALI, a_project_id, 4, ali_salt, 2)
+                .unwrap_err(),
+            VotingError::CommitmentDoesNotMatch
+        );
+        voting
+            .reveal_vote(ALI, a_project_id, 3, ali_salt, 2)
+            .unwrap();
+        let mut votes = BTreeMap::new();
+        votes.insert(a_project_id, 3);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 3,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+        assert_eq!(
+            voting
+                .reveal_vote(ALI, a_project_id, 3, ali_salt, 2)
+                .unwrap_err(),
+            VotingError::CommitmentDoesNotMatch
+        );
+
+        // Unrevealed commitments are not counted.
+        assert_eq!(
+            voting
+                .reveal_vote(BOB, b_project_id, 5, bob_salt, 3)
+                .unwrap_err(),
+            VotingError::RevealEnded
+        );
+        assert_eq!(voting.results(2).unwrap_err(), VotingError::VotingNotEnded);
+        assert_eq!(
+            voting.results(3).unwrap(),
+            vec![(a_project_id, 3), (b_project_id, 0)]
+        );
+        voting.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn test_one_commitment_per_participant() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        voting.enable_secret_ballot(3).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        let salt = [1u8; 32];
+
+        // Committing again replaces the previous commitment.
+        let a_commitment = vote_commitment(a_project_id, 5, salt);
+        let b_commitment = vote_commitment(b_project_id, 5, salt);
+        voting.commit_vote(ALI, a_commitment, 1).unwrap();
+        voting.commit_vote(ALI, b_commitment, 1).unwrap();
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap().commitment,
+            Some(b_commitment)
+        );
+        assert_eq!(
+            voting
+                .reveal_vote(ALI, a_project_id, 5, salt, 2)
+                .unwrap_err(),
+            VotingError::CommitmentDoesNotMatch
+        );
+        voting.reveal_vote(ALI, b_project_id, 5, salt, 2).unwrap();
+    }
+
+    #[test]
+    fn test_commit_vote_in_public_ballot() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        let salt = [1u8; 32];
+        assert_eq!(
+            voting
+                .commit_vote(ALI, vote_commitment(project_id, 1, salt), 1)
+                .unwrap_err(),
+            VotingError::WrongVoteMode
+        );
+        assert_eq!(
+            voting.reveal_vote(ALI, project_id, 1, salt, 2).unwrap_err(),
+            VotingError::WrongVoteMode
+        );
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, project_id, 3, 1).unwrap();
+        voting.check_invariants().unwrap();
+
+        // Used voting power has to match the votes.
+        voting.participants.get_mut(&ALI).unwrap().used_voting_power = 2;
+        assert_eq!(
+            voting.check_invariants().unwrap_err(),
+            VotingError::InvariantViolated
+        );
+
+        // Used voting power cannot exceed the total voting power.
+        voting.participants.get_mut(&ALI).unwrap().used_voting_power = 6;
+        voting
+            .participants
+            .get_mut(&ALI)
+            .unwrap()
+            .votes
+            .insert(project_id, 6);
+        assert_eq!(
+            voting.check_invariants().unwrap_err(),
+            VotingError::InvariantViolated
+        );
+
+        // Votes can be casted only on existing projects.
+        voting.participants.get_mut(&ALI).unwrap().used_voting_power = 3;
+        voting
+            .participants
+            .get_mut(&ALI)
+            .unwrap()
+            .votes
+            .insert(project_id, 3);
+        voting.projects.remove(&project_id);
+        assert_eq!(
+            voting.check_invariants().unwrap_err(),
+            VotingError::InvariantViolated
+        );
+    }
+
+    #[test]
+    fn test_retract_vote() {
+        let mut voting = Voting::new(1, 3).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, a_project_id, 2, 1).unwrap();
+        voting.cast_vote(ALI, b_project_id, 3, 1).unwrap();
+
+        // Cannot retract a vote that was not casted.
+        assert_eq!(
+            voting.retract_vote(BOB, a_project_id, 1).unwrap_err(),
+            VotingError::NotAParticipant
+        );
+        assert_eq!(
+            voting.retract_vote(ALI, ProjectId(3), 1).unwrap_err(),
+            VotingError::VoteDoesNotExist
+        );
+
+        // Cannot retract after voting ends.
+        assert_eq!(
+            voting.retract_vote(ALI, a_project_id, 3).unwrap_err(),
+            VotingError::VotingEnded
+        );
+
+        // Retracting gives the voting power back.
+        voting.retract_vote(ALI, a_project_id, 2).unwrap();
+        let mut votes = BTreeMap::new();
+        votes.insert(b_project_id, 3);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 3,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+        assert_eq!(
+            voting.retract_vote(ALI, a_project_id, 2).unwrap_err(),
+            VotingError::VoteDoesNotExist
+        );
+        voting.cast_vote(ALI, a_project_id, 2, 2).unwrap();
+    }
+
+    #[test]
+    fn test_team_members() {
+        let mut voting = Voting::new(1, 3).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, a_project_id, 2, 1).unwrap();
+
+        // Only participants can be linked to existing projects.
+        assert_eq!(
+            voting.add_team_member(BOB, a_project_id).unwrap_err(),
+            VotingError::NotAParticipant
+        );
+        assert_eq!(
+            voting.add_team_member(ALI, ProjectId(3)).unwrap_err(),
+            VotingError::ProjectDoesNotExists {
+                project_id: ProjectId(3)
+            }
+        );
+
+        // Linking cancels the votes for the own project.
+        voting.add_team_member(ALI, a_project_id).unwrap();
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 0,
+                votes: BTreeMap::new(),
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: Some(a_project_id)
+            }
+        );
+
+        // Cannot vote for the own project.
+        assert_eq!(
+            voting.cast_vote(ALI, a_project_id, 2, 1).unwrap_err(),
+            VotingError::CannotVoteForOwnProject
+        );
+        voting.cast_vote(ALI, b_project_id, 2, 1).unwrap();
+        assert_eq!(
+            voting
+                .change_vote(ALI, b_project_id, a_project_id, 1)
+                .unwrap_err(),
+            VotingError::CannotVoteForOwnProject
+        );
+        voting.check_invariants().unwrap();
+
+        // Team membership ends with the project.
+        voting
+            .remove_project_if_exists_and_cancel_votes(a_project_id)
+            .unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().team, None);
+
+        voting.add_team_member(ALI, b_project_id).unwrap();
+        voting.remove_team_member(ALI).unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().team, None);
+        voting.cast_vote(ALI, b_project_id, 2, 1).unwrap();
+    }
+
+    #[test]
+    fn test_team_member_ranking() {
+        let mut voting = Voting::with_vote_mode(1, 3, VoteMode::Ranked).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 0).unwrap();
+        voting
+            .cast_ranked_vote(ALI, vec![a_project_id, b_project_id], 1)
+            .unwrap();
+        voting.add_team_member(ALI, a_project_id).unwrap();
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap().ranking,
+            vec![b_project_id]
+        );
+        assert_eq!(
+            voting
+                .cast_ranked_vote(ALI, vec![b_project_id, a_project_id], 1)
+                .unwrap_err(),
+            VotingError::CannotVoteForOwnProject
+        );
+    }
+
+    #[test]
+    fn test_delegation() {
+        let mut voting = Voting::new(1, 3).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        voting.add_or_update_participant(JON, 5).unwrap();
+
+        // Only participants can delegate and receive voting power.
+        let unknown = PublicKey::ed25519_from([4u8; 32]);
+        assert_eq!(
+            voting
+                .delegate_voting_power(unknown, ALI, 1, 1)
+                .unwrap_err(),
+            VotingError::NotAParticipant
+        );
+        assert_eq!(
+            voting
+                .delegate_voting_power(ALI, unknown, 1, 1)
+                .unwrap_err(),
+            VotingError::NotAParticipant
+        );
+
+        // Cannot delegate more than the free voting power.
+        voting.cast_vote(ALI, project_id, 2, 1).unwrap();
+        assert_eq!(
+            voting.delegate_voting_power(ALI, BOB, 4, 1).unwrap_err(),
+            VotingError::NotEnoughVotingPower {
+                required: 4,
+                available: 3
+            }
+        );
+
+        // Delegation can be done before the voting starts.
+        voting.delegate_voting_power(ALI, BOB, 3, 0).unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().voting_power(), 2);
+        assert_eq!(voting.participants.get(&BOB).unwrap().voting_power(), 8);
+
+        // Received power can be passed further down the chain.
+        voting.delegate_voting_power(BOB, JON, 8, 1).unwrap();
+        assert_eq!(voting.participants.get(&JON).unwrap().voting_power(), 13);
+        voting.cast_vote(JON, project_id, 13, 1).unwrap();
+
+        // Chain cannot go back to the delegator.
+        assert_eq!(
+            voting.delegate_voting_power(JON, ALI, 1, 1).unwrap_err(),
+            VotingError::DelegationCycle
+        );
+        assert_eq!(
+            voting.delegate_voting_power(ALI, ALI, 1, 1).unwrap_err(),
+            VotingError::DelegationCycle
+        );
+        voting.check_invariants().unwrap();
+
+        // Cannot delegate after voting ends.
+        assert_eq!(
+            voting.delegate_voting_power(ALI, JON, 1, 3).unwrap_err(),
+            VotingError::VotingEnded
+        );
+        assert_eq!(
+            voting.revoke_delegation(ALI, BOB, 3).unwrap_err(),
+            VotingError::VotingEnded
+        );
+        assert_eq!(
+            voting.revoke_delegation(ALI, JON, 2).unwrap_err(),
+            VotingError::DelegationDoesNotExist
+        );
+
+        // Revoking follows the chain and cancels votes that are no longer covered.
+        voting.revoke_delegation(ALI, BOB, 2).unwrap();
+        assert_eq!(
+            voting.participants.get(&BOB).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 0,
+                votes: BTreeMap::new(),
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+        assert_eq!(
+            voting.participants.get(&JON).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 0,
+                votes: BTreeMap::new(),
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+        voting.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn test_delegation_and_participant_changes() {
+        let mut voting = Voting::new(1, 3).unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        voting.add_or_update_participant(JON, 5).unwrap();
+        voting.delegate_voting_power(ALI, BOB, 2, 1).unwrap();
+        voting.delegate_voting_power(BOB, JON, 6, 1).unwrap();
+
+        // Updating the participant revokes its delegations.
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().voting_power(), 3);
+        assert_eq!(voting.participants.get(&BOB).unwrap().voting_power(), 7);
+        assert_eq!(voting.participants.get(&JON).unwrap().voting_power(), 5);
+
+        // Removing the participant gives the delegated power back.
+        voting.remove_participant_if_exists(&BOB).unwrap();
+        assert_eq!(voting.participants.get(&ALI).unwrap().voting_power(), 5);
+        voting.check_invariants().unwrap();
+
+        // Delegation makes no sense when voting power is not used.
+        let mut voting = Voting::with_vote_mode(1, 3, VoteMode::Ranked).unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        assert_eq!(
+            voting.delegate_voting_power(ALI, BOB, 1, 1).unwrap_err(),
+            VotingError::WrongVoteMode
+        );
+    }
+
+    #[test]
+    fn test_change_vote() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        let c_project_id = ProjectId(3);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(c_project_id, example_project("c_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, a_project_id, 2, 1).unwrap();
+        voting.cast_vote(ALI, b_project_id, 3, 1).unwrap();
+
+        // Cannot move votes to non existing project.
+        assert_eq!(
+            voting
+                .change_vote(ALI, a_project_id, ProjectId(4), 1)
+                .unwrap_err(),
+            VotingError::ProjectDoesNotExists {
+                project_id: ProjectId(4)
+            }
+        );
+
+        // Cannot move votes that were not casted.
+        assert_eq!(
+            voting
+                .change_vote(ALI, c_project_id, a_project_id, 1)
+                .unwrap_err(),
+            VotingError::VoteDoesNotExist
+        );
+
+        // Move votes to a new project.
+        voting
+            .change_vote(ALI, a_project_id, c_project_id, 1)
+            .unwrap();
+        let mut votes = BTreeMap::new();
+        votes.insert(b_project_id, 3);
+        votes.insert(c_project_id, 2);
+        assert_eq!(voting.participants.get(&ALI).unwrap().votes, votes);
+
+        // Move votes to a project that already has votes.
+        voting
+            .change_vote(ALI, b_project_id, c_project_id, 1)
+            .unwrap();
+        let mut votes = BTreeMap::new();
+        votes.insert(c_project_id, 5);
+        assert_eq!(
+            voting.participants.get(&ALI).unwrap(),
+            &Participant {
+                total_voting_power: 5,
+                used_voting_power: 5,
+                votes,
+                ranking: Vec::new(),
+                commitment: None,
+                delegations: BTreeMap::new(),
+                received_voting_power: 0,
+                team: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_finalize() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, project_id, 5, 1).unwrap();
+
+        // Cannot finalize before voting ends.
+        assert_eq!(voting.finalize(1).unwrap_err(), VotingError::VotingNotEnded);
+        assert!(!voting.is_finalized());
+        voting.finalize(2).unwrap();
+        assert!(voting.is_finalized());
+        assert_eq!(
+            voting.finalize(2).unwrap_err(),
+            VotingError::VotingFinalized
+        );
+
+        // Finalized voting cannot be changed.
+        assert_eq!(
+            voting.add_or_update_participant(ALI, 10).unwrap_err(),
+            VotingError::VotingFinalized
+        );
+        assert_eq!(
+            voting.remove_participant_if_exists(&ALI).unwrap_err(),
+            VotingError::VotingFinalized
+        );
+        assert_eq!(
+            voting
+                .add_or_update_project(project_id, example_project("project2"))
+                .unwrap_err(),
+            VotingError::VotingFinalized
+        );
+        assert_eq!(
+            voting
+                .remove_project_if_exists_and_cancel_votes(project_id)
+                .unwrap_err(),
+            VotingError::VotingFinalized
+        );
+        assert_eq!(voting.results(2).unwrap(), vec![(project_id, 5)]);
+    }
+
+    #[test]
+    fn test_tally_and_leaderboard() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        let c_project_id = ProjectId(3);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(c_project_id, example_project("c_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        voting.cast_vote(ALI, a_project_id, 2, 1).unwrap();
+        voting.cast_vote(ALI, b_project_id, 3, 1).unwrap();
+        voting.cast_vote(BOB, a_project_id, 3, 1).unwrap();
+
+        let mut tally = BTreeMap::new();
+        tally.insert(a_project_id, 5);
+        tally.insert(b_project_id, 3);
+        tally.insert(c_project_id, 0);
+        assert_eq!(voting.tally(), tally);
+
+        assert_eq!(
+            voting.leaderboard(),
+            vec![(a_project_id, 5), (b_project_id, 3), (c_project_id, 0)]
+        );
+
+        // Ties are ordered by the project id.
+        voting.cast_vote(BOB, c_project_id, 2, 1).unwrap();
+        voting
+            .remove_project_if_exists_and_cancel_votes(b_project_id)
+            .unwrap();
+        assert_eq!(
+            voting.leaderboard(),
+            vec![(a_project_id, 5), (c_project_id, 2)]
+        );
+        voting.cast_vote(ALI, c_project_id, 3, 1).unwrap();
+        assert_eq!(
+            voting.leaderboard(),
+            vec![(a_project_id, 5), (c_project_id, 5)]
+        );
+    }
+
+    #[test]
+    fn test_tally_saturates() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, u64::MAX).unwrap();
+        voting.add_or_update_participant(BOB, u64::MAX).unwrap();
+        voting.cast_vote(ALI, project_id, u64::MAX, 1).unwrap();
+        voting.cast_vote(BOB, project_id, u64::MAX, 1).unwrap();
+        assert_eq!(voting.tally().get(&project_id), Some(&u64::MAX));
+    }
+
+    #[test]
+    fn test_remaining_voting_power() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 10).unwrap();
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        assert_eq!(
+            voting.remaining_voting_power(&JON).unwrap_err(),
+            VotingError::NotAParticipant
+        );
+
+        // Both votes and delegations use the voting power.
+        voting.cast_vote(ALI, project_id, 3, 1).unwrap();
+        voting.delegate_voting_power(ALI, BOB, 2, 1).unwrap();
+        assert_eq!(voting.remaining_voting_power(&ALI).unwrap(), 5);
+        assert_eq!(voting.remaining_voting_power(&BOB).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_results() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, project_id, 5, 1).unwrap();
+
+        // Results are not available before voting ends.
+        assert_eq!(voting.results(1).unwrap_err(), VotingError::VotingNotEnded);
+        assert_eq!(voting.results(2).unwrap(), vec![(project_id, 5)]);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Replace).unwrap();
+        let project_id = ProjectId(1);
+        let project = example_project("project");
+        let ali_power = 10;
+        voting.add_or_update_project(project_id, project).unwrap();
+        voting.add_or_update_participant(ALI, ali_power).unwrap();
+        voting.cast_vote(ALI, project_id, ali_power, 1).unwrap();
+        voting.finalize(2).unwrap();
+
+        let serialized = voting.serialize();
+        let deserialized = Voting::deserialize(serialized).unwrap();
+        assert_eq!(voting, deserialized);
+    }
+
+    #[test]
+    fn test_entry_serialization() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        let project = example_project("project");
+        voting
+            .add_or_update_project(project_id, project.clone())
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.cast_vote(ALI, project_id, 5, 1).unwrap();
+
+        let serialized = Voting::serialize_project(&project);
+        assert_eq!(Voting::deserialize_project(serialized), project);
+        let participant = voting.participants.get(&ALI).unwrap();
+        let serialized = Voting::serialize_participant(participant);
+        assert_eq!(&Voting::deserialize_participant(serialized), participant);
+        let results = voting.results(2).unwrap();
+        let serialized = Voting::serialize_results(&results);
+        assert_eq!(Voting::deserialize_results(serialized), results);
+    }
+
+    #[test]
+    fn test_approval_serialization() {
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Approval(3)).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 0).unwrap();
+        voting.cast_vote(ALI, project_id, 1, 1).unwrap();
+
+        let serialized = voting.serialize();
+        let deserialized = Voting::deserialize(serialized).unwrap();
+        assert_eq!(voting, deserialized);
+    }
+
+    #[test]
+    fn test_secret_ballot_serialization() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        voting.enable_secret_ballot(3).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting
+            .commit_vote(ALI, vote_commitment(project_id, 5, [1u8; 32]), 1)
+            .unwrap();
+
+        let serialized = voting.serialize();
+        let deserialized = Voting::deserialize(serialized).unwrap();
+        assert_eq!(voting, deserialized);
+    }
+
+    #[test]
+    fn test_delegation_serialization() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.add_or_update_participant(BOB, 5).unwrap();
+        voting.delegate_voting_power(ALI, BOB, 3, 1).unwrap();
+
+        let serialized = voting.serialize();
+        let deserialized = Voting::deserialize(serialized).unwrap();
+        assert_eq!(voting, deserialized);
+    }
+
+    #[test]
+    fn test_team_serialization() {
+        let mut voting = Voting::new(1, 2).unwrap();
+        let project_id = ProjectId(1);
+        voting
+            .add_or_update_project(project_id, example_project("project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 5).unwrap();
+        voting.add_team_member(ALI, project_id).unwrap();
+
+        let serialized = voting.serialize();
+        let deserialized = Voting::deserialize(serialized).unwrap();
+        assert_eq!(voting, deserialized);
+    }
+
+    #[test]
+    fn test_ranked_serialization() {
+        let mut voting = Voting::with_vote_mode(1, 2, VoteMode::Ranked).unwrap();
+        let a_project_id = ProjectId(1);
+        let b_project_id = ProjectId(2);
+        voting
+            .add_or_update_project(a_project_id, example_project("a_project"))
+            .unwrap();
+        voting
+            .add_or_update_project(b_project_id, example_project("b_project"))
+            .unwrap();
+        voting.add_or_update_participant(ALI, 1).unwrap();
+        voting
+            .cast_ranked_vote(ALI, vec![b_project_id, a_project_id], 1)
+            .unwrap();
+
+        let serialized = voting.serialize();
+        let deserialized = Voting::deserialize(serialized).unwrap();
+        assert_eq!(voting, deserialized);
+    }
+
+    #[test]
+    fn test_deserialize_unsupported_version() {
+        let voting = Voting::new(1, 2).unwrap();
+        let mut serialized = voting.serialize();
+        serialized.0 = VERSION + 1;
+        assert_eq!(
+            Voting::deserialize(serialized.clone()).unwrap_err(),
+            VotingError::UnsupportedVersion
+        );
+
+        serialized.0 = VERSION - 1;
+        assert_eq!(
+            Voting::deserialize(serialized).unwrap_err(),
+            VotingError::UnsupportedVersion
+        );
+    }
+
+    #[test]
+    fn test_deserialize_unknown_vote_mode() {
+        let voting = Voting::new(1, 2).unwrap();
+        let mut serialized = voting.serialize();
+        ((serialized.1).2).0 = 5;
+        assert_eq!(
+            Voting::deserialize(serialized).unwrap_err(),
+            VotingError::UnsupportedVersion
+        );
+    }
+
+    #[test]
+    fn test_deserialize_missing_project_fields() {
+        let mut fields = BTreeMap::new();
+        fields.insert(String::from(NAME), String::from("project"));
+        let mut projects = BTreeMap::new();
+        projects.insert(1, fields);
+        let project = Voting::deserialize_projects(projects)
+            .remove(&ProjectId(1))
+            .unwrap();
+        assert_eq!(project.name, "project");
+        assert_eq!(project.team_name, "");
+        assert!(project.attributes.is_empty());
     }
 }
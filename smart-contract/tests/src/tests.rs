@@ -1,5 +1,5 @@
-use crate::voting::{account, Sender, VotingContract};
-use logic::{Participant, Project, ProjectId};
+use crate::voting::{account, role, vote_mode, Sender, VotingContract};
+use logic::{Participant, Project, ProjectId, VERSION};
 use std::collections::BTreeMap;
 
 pub const START_AT: u64 = 1;
@@ -19,6 +19,28 @@ fn test_init_with_start_after_end() {
     VotingContract::deployed(END_AT, START_AT);
 }
 
+#[test]
+fn test_set_voting_window() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_voting_window(START_AT + 1, END_AT + 1, Sender(account::ADMIN));
+    assert_eq!(voting.start_at(), START_AT + 1);
+    assert_eq!(voting.end_at(), END_AT + 1);
+}
+
+#[test]
+#[should_panic]
+fn test_set_voting_window_with_start_after_end() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_voting_window(END_AT, START_AT, Sender(account::ADMIN));
+}
+
+#[test]
+#[should_panic]
+fn test_set_voting_window_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_voting_window(START_AT, END_AT + 1, Sender(account::ALI));
+}
+
 #[test]
 fn test_add_and_update_participant() {
     let mut voting = VotingContract::deployed(START_AT, END_AT);
@@ -28,7 +50,12 @@ fn test_add_and_update_participant() {
         Participant {
             total_voting_power: VOTING_POWER,
             used_voting_power: 0,
-            votes: BTreeMap::new()
+            votes: BTreeMap::new(),
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
         }
     );
     let new_voting_power = 11;
@@ -38,7 +65,12 @@ fn test_add_and_update_participant() {
         Participant {
             total_voting_power: new_voting_power,
             used_voting_power: 0,
-            votes: BTreeMap::new()
+            votes: BTreeMap::new(),
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
         }
     );
 }
@@ -66,6 +98,44 @@ fn test_remove_participant_by_none_admin() {
     voting.remove_participant(account::ALI, Sender(account::ALI));
 }
 
+#[test]
+fn test_add_or_update_participants() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.add_or_update_participants(
+        vec![
+            (account::ALI, VOTING_POWER),
+            (account::BOB, VOTING_POWER + 1),
+        ],
+        Sender(account::ADMIN),
+    );
+    assert_eq!(
+        voting.participant(account::ALI).unwrap().total_voting_power,
+        VOTING_POWER
+    );
+    assert_eq!(
+        voting.participant(account::BOB).unwrap().total_voting_power,
+        VOTING_POWER + 1
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_add_or_update_participants_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.add_or_update_participants(vec![(account::ALI, VOTING_POWER)], Sender(account::ALI));
+}
+
+#[test]
+fn test_add_or_update_projects() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let mut projects = BTreeMap::new();
+    projects.insert(ProjectId(1), example_project("a"));
+    projects.insert(ProjectId(2), example_project("b"));
+    voting.add_or_update_projects(projects, Sender(account::ADMIN));
+    assert_eq!(voting.project(ProjectId(1)).unwrap(), example_project("a"));
+    assert_eq!(voting.project(ProjectId(2)).unwrap(), example_project("b"));
+}
+
 #[test]
 fn test_add_and_update_project() {
     let mut voting = VotingContract::deployed(START_AT, END_AT);
@@ -78,6 +148,16 @@ fn test_add_and_update_project() {
     assert_eq!(voting.project(project_id).unwrap(), updated_project);
 }
 
+#[test]
+fn test_add_project_with_arguments_in_any_order() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.add_project_without_attributes(ProjectId(1), "project", "team", Sender(account::ADMIN));
+    let project = voting.project(ProjectId(1)).unwrap();
+    assert_eq!(project.name, "project");
+    assert_eq!(project.team_name, "team");
+    assert!(project.attributes.is_empty());
+}
+
 #[test]
 #[should_panic]
 fn test_add_and_update_project_by_non_admin() {
@@ -87,6 +167,84 @@ fn test_add_and_update_project_by_non_admin() {
     voting.add_or_update_project(project_id, project, Sender(account::ALI));
 }
 
+#[test]
+#[should_panic]
+fn test_add_project_with_empty_name() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.add_or_update_project(ProjectId(1), example_project(""), Sender(account::ADMIN));
+}
+
+#[test]
+#[should_panic]
+fn test_add_project_with_invalid_link() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let mut project = example_project("project");
+    project
+        .attributes
+        .insert("video".to_string(), "youtube".to_string());
+    voting.add_or_update_project(ProjectId(1), project, Sender(account::ADMIN));
+}
+
+#[test]
+fn test_set_and_delete_project_attribute() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_project_attribute(project_id, "track", "defi", Sender(account::ADMIN));
+    assert_eq!(
+        voting.project(project_id).unwrap().attributes["track"],
+        "defi"
+    );
+    voting.delete_project_attribute(project_id, "track", Sender(account::ADMIN));
+    assert_eq!(
+        voting.project(project_id).unwrap(),
+        example_project("project")
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_set_project_attribute_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_project_attribute(project_id, "track", "defi", Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_set_reserved_project_attribute() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_project_attribute(project_id, "name", "other", Sender(account::ADMIN));
+}
+
+#[test]
+#[should_panic]
+fn test_delete_missing_project_attribute() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.delete_project_attribute(project_id, "track", Sender(account::ADMIN));
+}
+
 #[test]
 fn test_remove_project() {
     let mut voting = VotingContract::deployed(START_AT, END_AT);
@@ -107,6 +265,55 @@ fn test_remove_project_by_non_admin() {
     voting.remove_project(project_id, Sender(account::ALI));
 }
 
+#[test]
+fn test_add_and_remove_team_member() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.add_team_member(account::ALI, project_id, Sender(account::ADMIN));
+    assert_eq!(
+        voting.participant(account::ALI).unwrap().team,
+        Some(project_id)
+    );
+    voting.remove_team_member(account::ALI, Sender(account::ADMIN));
+    assert_eq!(voting.participant(account::ALI).unwrap().team, None);
+}
+
+#[test]
+#[should_panic]
+fn test_add_team_member_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.add_team_member(account::ALI, project_id, Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_vote_casting_for_own_project() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.add_team_member(account::ALI, project_id, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, 1, Sender(account::ALI));
+}
+
 #[test]
 fn test_vote_casting() {
     let mut voting = VotingContract::deployed(START_AT, END_AT);
@@ -123,17 +330,844 @@ fn test_vote_casting() {
         Participant {
             total_voting_power: VOTING_POWER,
             used_voting_power: VOTING_POWER,
-            votes
+            votes,
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+}
+
+#[test]
+fn test_vote_casting_twice_with_accumulate_mode() {
+    let mut voting =
+        VotingContract::deployed_with_vote_mode(START_AT, END_AT, vote_mode::ACCUMULATE);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, 3, Sender(account::ALI));
+    voting.cast_vote(project_id, 4, Sender(account::ALI));
+    let mut votes = BTreeMap::new();
+    votes.insert(project_id, 7);
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: VOTING_POWER,
+            used_voting_power: 7,
+            votes,
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+}
+
+#[test]
+fn test_vote_casting_twice_with_replace_mode() {
+    let mut voting = VotingContract::deployed_with_vote_mode(START_AT, END_AT, vote_mode::REPLACE);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, VOTING_POWER, Sender(account::ALI));
+    voting.cast_vote(project_id, 4, Sender(account::ALI));
+    let mut votes = BTreeMap::new();
+    votes.insert(project_id, 4);
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: VOTING_POWER,
+            used_voting_power: 4,
+            votes,
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+}
+
+#[test]
+fn test_vote_casting_with_quadratic_mode() {
+    let mut voting =
+        VotingContract::deployed_with_vote_mode(START_AT, END_AT, vote_mode::QUADRATIC);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, 2, Sender(account::ALI));
+    voting.cast_vote(project_id, 1, Sender(account::ALI));
+    let mut votes = BTreeMap::new();
+    votes.insert(project_id, 3);
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: VOTING_POWER,
+            used_voting_power: 9,
+            votes,
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_vote_casting_with_quadratic_mode_over_the_budget() {
+    let mut voting =
+        VotingContract::deployed_with_vote_mode(START_AT, END_AT, vote_mode::QUADRATIC);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, 4, Sender(account::ALI));
+}
+
+#[test]
+fn test_ranked_vote_casting() {
+    let mut voting = VotingContract::deployed_with_vote_mode(START_AT, END_AT, vote_mode::RANKED);
+    let a_project_id = ProjectId(1);
+    let b_project_id = ProjectId(2);
+    voting.add_or_update_project(a_project_id, example_project("a"), Sender(account::ADMIN));
+    voting.add_or_update_project(b_project_id, example_project("b"), Sender(account::ADMIN));
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.add_or_update_participant(account::BOB, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_ranked_vote(vec![b_project_id, a_project_id], Sender(account::ALI));
+    voting.cast_ranked_vote(vec![b_project_id], Sender(account::BOB));
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: VOTING_POWER,
+            used_voting_power: 0,
+            votes: BTreeMap::new(),
+            ranking: vec![b_project_id, a_project_id],
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+    voting.set_block_time(END_AT);
+    voting.publish_results(Sender(account::ADMIN));
+    assert_eq!(
+        voting.results().unwrap(),
+        vec![(b_project_id, 2), (a_project_id, 0)]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_power_vote_casting_with_ranked_mode() {
+    let mut voting = VotingContract::deployed_with_vote_mode(START_AT, END_AT, vote_mode::RANKED);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, VOTING_POWER, Sender(account::ALI));
+}
+
+#[test]
+fn test_approval_vote_casting() {
+    let mut voting =
+        VotingContract::deployed_with_approval_limit(START_AT, END_AT, vote_mode::APPROVAL, 1);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, 0, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, 1, Sender(account::ALI));
+    let mut votes = BTreeMap::new();
+    votes.insert(project_id, 1);
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: 0,
+            used_voting_power: 1,
+            votes,
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
         }
     );
 }
 
+#[test]
+#[should_panic]
+fn test_approval_vote_casting_over_the_limit() {
+    let mut voting =
+        VotingContract::deployed_with_approval_limit(START_AT, END_AT, vote_mode::APPROVAL, 1);
+    let a_project_id = ProjectId(1);
+    let b_project_id = ProjectId(2);
+    voting.add_or_update_project(a_project_id, example_project("a"), Sender(account::ADMIN));
+    voting.add_or_update_project(b_project_id, example_project("b"), Sender(account::ADMIN));
+    voting.add_or_update_participant(account::ALI, 0, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(a_project_id, 1, Sender(account::ALI));
+    voting.cast_vote(b_project_id, 1, Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_init_with_unknown_vote_mode() {
+    VotingContract::deployed_with_vote_mode(START_AT, END_AT, "unknown");
+}
+
+#[test]
+fn test_secret_ballot() {
+    let reveal_end_at = END_AT + 1;
+    let mut voting = VotingContract::deployed_with_secret_ballot(START_AT, END_AT, reveal_end_at);
+    let project_id = ProjectId(1);
+    let salt = [7u8; 32];
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.commit_vote(project_id, 3, salt, Sender(account::ALI));
+    assert_eq!(
+        voting.participant(account::ALI).unwrap().used_voting_power,
+        0
+    );
+    voting.set_block_time(END_AT);
+    voting.reveal_vote(project_id, 3, salt, Sender(account::ALI));
+    let mut votes = BTreeMap::new();
+    votes.insert(project_id, 3);
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: VOTING_POWER,
+            used_voting_power: 3,
+            votes,
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+    voting.set_block_time(reveal_end_at);
+    voting.publish_results(Sender(account::ALI));
+    assert_eq!(voting.results().unwrap(), vec![(project_id, 3)]);
+}
+
+#[test]
+#[should_panic]
+fn test_secret_ballot_reveal_with_wrong_vote() {
+    let mut voting = VotingContract::deployed_with_secret_ballot(START_AT, END_AT, END_AT + 1);
+    let project_id = ProjectId(1);
+    let salt = [7u8; 32];
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.commit_vote(project_id, 3, salt, Sender(account::ALI));
+    voting.set_block_time(END_AT);
+    voting.reveal_vote(project_id, 4, salt, Sender(account::ALI));
+}
+
+#[test]
+fn test_delegation() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.add_or_update_participant(account::BOB, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.delegate_voting_power(account::BOB, 4, Sender(account::ALI));
+    voting.cast_vote(project_id, VOTING_POWER + 4, Sender(account::BOB));
+    assert_eq!(
+        voting
+            .participant(account::BOB)
+            .unwrap()
+            .received_voting_power,
+        4
+    );
+    voting.revoke_delegation(account::BOB, Sender(account::ALI));
+    let bob = voting.participant(account::BOB).unwrap();
+    assert_eq!(bob.received_voting_power, 0);
+    assert_eq!(bob.used_voting_power, 0);
+}
+
+#[test]
+#[should_panic]
+fn test_delegation_cycle() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.add_or_update_participant(account::BOB, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.delegate_voting_power(account::BOB, 1, Sender(account::ALI));
+    voting.delegate_voting_power(account::ALI, 1, Sender(account::BOB));
+}
+
+#[test]
+fn test_vote_retracting() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, VOTING_POWER, Sender(account::ALI));
+    voting.retract_vote(project_id, Sender(account::ALI));
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: VOTING_POWER,
+            used_voting_power: 0,
+            votes: BTreeMap::new(),
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_vote_retracting_without_vote() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.retract_vote(project_id, Sender(account::ALI));
+}
+
+#[test]
+fn test_vote_changing() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let a_project_id = ProjectId(1);
+    let b_project_id = ProjectId(2);
+    voting.add_or_update_project(a_project_id, example_project("a"), Sender(account::ADMIN));
+    voting.add_or_update_project(b_project_id, example_project("b"), Sender(account::ADMIN));
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(a_project_id, VOTING_POWER, Sender(account::ALI));
+    voting.change_vote(a_project_id, b_project_id, Sender(account::ALI));
+    let mut votes = BTreeMap::new();
+    votes.insert(b_project_id, VOTING_POWER);
+    assert_eq!(
+        voting.participant(account::ALI).unwrap(),
+        Participant {
+            total_voting_power: VOTING_POWER,
+            used_voting_power: VOTING_POWER,
+            votes,
+            ranking: Vec::new(),
+            commitment: None,
+            delegations: BTreeMap::new(),
+            received_voting_power: 0,
+            team: None
+        }
+    );
+}
+
+#[test]
+fn test_publish_results() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let a_project_id = ProjectId(1);
+    let b_project_id = ProjectId(2);
+    voting.add_or_update_project(a_project_id, example_project("a"), Sender(account::ADMIN));
+    voting.add_or_update_project(b_project_id, example_project("b"), Sender(account::ADMIN));
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.add_or_update_participant(account::BOB, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(a_project_id, 3, Sender(account::ALI));
+    voting.cast_vote(b_project_id, 7, Sender(account::ALI));
+    voting.cast_vote(a_project_id, 2, Sender(account::BOB));
+    voting.set_block_time(END_AT);
+    voting.publish_results(Sender(account::BOB));
+    assert_eq!(
+        voting.results().unwrap(),
+        vec![(b_project_id, 7), (a_project_id, 5)]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_publish_results_before_end() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_block_time(START_AT);
+    voting.publish_results(Sender(account::ADMIN));
+}
+
+#[test]
+fn test_queries() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    let project = example_project("project");
+    voting.add_or_update_project(project_id, project.clone(), Sender(account::ADMIN));
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, 3, Sender(account::ALI));
+
+    // Anyone can ask, not only the admin.
+    assert_eq!(
+        voting.get_project(project_id, Sender(account::BOB)),
+        project
+    );
+    assert_eq!(
+        voting.get_participant(account::ALI, Sender(account::BOB)),
+        voting.participant(account::ALI).unwrap()
+    );
+    assert_eq!(
+        voting.get_remaining_power(account::ALI, Sender(account::BOB)),
+        VOTING_POWER - 3
+    );
+    voting.set_block_time(END_AT);
+    assert_eq!(
+        voting.get_results(Sender(account::BOB)),
+        vec![(project_id, 3)]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_get_missing_project() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.get_project(ProjectId(1), Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_get_results_before_end() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_block_time(START_AT);
+    voting.get_results(Sender(account::ALI));
+}
+
+#[test]
+fn test_finalize() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    assert!(!voting.is_finalized());
+    voting.set_block_time(END_AT);
+    voting.finalize(Sender(account::ADMIN));
+    assert!(voting.is_finalized());
+}
+
+#[test]
+#[should_panic]
+fn test_finalize_before_end() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_block_time(START_AT);
+    voting.finalize(Sender(account::ADMIN));
+}
+
+#[test]
+#[should_panic]
+fn test_finalize_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_block_time(END_AT);
+    voting.finalize(Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_remove_project_after_finalize() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_block_time(END_AT);
+    voting.finalize(Sender(account::ADMIN));
+    voting.remove_project(project_id, Sender(account::ADMIN));
+}
+
+#[test]
+fn test_pause_and_unpause() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    assert!(!voting.is_paused());
+    voting.set_block_time(START_AT);
+    voting.pause(Sender(account::ADMIN));
+    assert!(voting.is_paused());
+    voting.add_or_update_project(
+        project_id,
+        example_project("fixed project"),
+        Sender(account::ADMIN),
+    );
+    voting.unpause(Sender(account::ADMIN));
+    assert!(!voting.is_paused());
+    voting.cast_vote(project_id, 1, Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_pause_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.pause(Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_pause_twice() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.pause(Sender(account::ADMIN));
+    voting.pause(Sender(account::ADMIN));
+}
+
+#[test]
+#[should_panic]
+fn test_unpause_when_not_paused() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.unpause(Sender(account::ADMIN));
+}
+
+#[test]
+#[should_panic]
+fn test_vote_casting_when_paused() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.pause(Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.cast_vote(project_id, 1, Sender(account::ALI));
+}
+
+#[test]
+fn test_reveal_vote_when_paused() {
+    let mut voting = VotingContract::deployed_with_secret_ballot(START_AT, END_AT, END_AT + 1);
+    let project_id = ProjectId(1);
+    let salt = [7u8; 32];
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_block_time(START_AT);
+    voting.commit_vote(project_id, 3, salt, Sender(account::ALI));
+    voting.pause(Sender(account::ADMIN));
+    voting.set_block_time(END_AT);
+    voting.reveal_vote(project_id, 3, salt, Sender(account::ALI));
+    assert_eq!(
+        voting.participant(account::ALI).unwrap().used_voting_power,
+        3
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_update_project_during_voting_when_not_paused() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_block_time(START_AT);
+    voting.add_or_update_project(
+        project_id,
+        example_project("fixed project"),
+        Sender(account::ADMIN),
+    );
+}
+
+#[test]
+fn test_admin_handover() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.propose_admin(account::BOB, Sender(account::ADMIN));
+    assert!(voting.has_role(account::ADMIN, role::OWNER));
+    voting.accept_admin(Sender(account::BOB));
+    assert!(!voting.has_role(account::ADMIN, role::OWNER));
+    assert!(voting.has_role(account::BOB, role::OWNER));
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::BOB));
+}
+
+#[test]
+#[should_panic]
+fn test_old_admin_after_handover() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.propose_admin(account::BOB, Sender(account::ADMIN));
+    voting.accept_admin(Sender(account::BOB));
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+}
+
+#[test]
+#[should_panic]
+fn test_propose_admin_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.propose_admin(account::ALI, Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_accept_admin_by_not_proposed_account() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.propose_admin(account::BOB, Sender(account::ADMIN));
+    voting.accept_admin(Sender(account::ALI));
+}
+
+#[test]
+fn test_project_manager_role() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.grant_role(
+        account::ALI,
+        role::PROJECT_MANAGER_NAME,
+        Sender(account::ADMIN),
+    );
+    assert!(voting.has_role(account::ALI, role::PROJECT_MANAGER));
+    assert!(!voting.has_role(account::ALI, role::PARTICIPANT_MANAGER));
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(project_id, example_project("project"), Sender(account::ALI));
+    voting.remove_project(project_id, Sender(account::ALI));
+    assert!(voting.project(project_id).is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_project_manager_cannot_add_participant() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.grant_role(
+        account::ALI,
+        role::PROJECT_MANAGER_NAME,
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::BOB, VOTING_POWER, Sender(account::ALI));
+}
+
+#[test]
+fn test_participant_manager_role() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.grant_role(
+        account::ALI,
+        role::PARTICIPANT_MANAGER_NAME,
+        Sender(account::ADMIN),
+    );
+    voting.add_or_update_participant(account::BOB, VOTING_POWER, Sender(account::ALI));
+    assert!(voting.participant(account::BOB).is_some());
+    voting.revoke_role(
+        account::ALI,
+        role::PARTICIPANT_MANAGER_NAME,
+        Sender(account::ADMIN),
+    );
+    assert!(!voting.has_role(account::ALI, role::PARTICIPANT_MANAGER));
+}
+
+#[test]
+#[should_panic]
+fn test_participant_manager_cannot_pause() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.grant_role(
+        account::ALI,
+        role::PARTICIPANT_MANAGER_NAME,
+        Sender(account::ADMIN),
+    );
+    voting.pause(Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_grant_role_by_non_admin() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.grant_role(
+        account::ALI,
+        role::PROJECT_MANAGER_NAME,
+        Sender(account::ALI),
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_grant_unknown_role() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.grant_role(account::ALI, "owner", Sender(account::ADMIN));
+}
+
+#[test]
+fn test_remove_project_with_multisig() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_multisig(
+        vec![account::ADMIN, account::BOB],
+        2,
+        10,
+        Sender(account::ADMIN),
+    );
+    voting.remove_project(project_id, Sender(account::ADMIN));
+    assert!(voting.project(project_id).is_some());
+    voting.remove_project(project_id, Sender(account::BOB));
+    assert!(voting.project(project_id).is_none());
+}
+
+#[test]
+fn test_remove_participant_with_expired_approval() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.add_or_update_participant(account::ALI, VOTING_POWER, Sender(account::ADMIN));
+    voting.set_multisig(
+        vec![account::ADMIN, account::BOB],
+        2,
+        1,
+        Sender(account::ADMIN),
+    );
+    voting.remove_participant(account::ALI, Sender(account::ADMIN));
+    voting.set_block_time(1);
+    voting.remove_participant(account::ALI, Sender(account::BOB));
+    assert!(voting.participant(account::ALI).is_some());
+}
+
+#[test]
+#[should_panic]
+fn test_remove_project_with_multisig_by_non_approver() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_multisig(
+        vec![account::ADMIN, account::BOB],
+        2,
+        10,
+        Sender(account::ADMIN),
+    );
+    voting.remove_project(project_id, Sender(account::ALI));
+}
+
+#[test]
+fn test_change_multisig_needs_approvals() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    let project_id = ProjectId(1);
+    voting.add_or_update_project(
+        project_id,
+        example_project("project"),
+        Sender(account::ADMIN),
+    );
+    voting.set_multisig(
+        vec![account::ADMIN, account::BOB],
+        2,
+        10,
+        Sender(account::ADMIN),
+    );
+
+    // The Owner alone can't turn the multisig off nor become the only approver.
+    voting.set_multisig(Vec::new(), 0, 0, Sender(account::ADMIN));
+    voting.set_multisig(vec![account::ADMIN], 1, 10, Sender(account::ADMIN));
+    voting.remove_project(project_id, Sender(account::ADMIN));
+    assert!(voting.project(project_id).is_some());
+
+    // The second approval turns it off and the removal no longer waits.
+    voting.set_multisig(Vec::new(), 0, 0, Sender(account::BOB));
+    voting.remove_project(project_id, Sender(account::ADMIN));
+    assert!(voting.project(project_id).is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_set_multisig_by_non_approver() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_multisig(
+        vec![account::ADMIN, account::BOB],
+        2,
+        10,
+        Sender(account::ADMIN),
+    );
+    voting.set_multisig(Vec::new(), 0, 0, Sender(account::ALI));
+}
+
+#[test]
+#[should_panic]
+fn test_set_multisig_with_too_high_threshold() {
+    let mut voting = VotingContract::deployed(START_AT, END_AT);
+    voting.set_multisig(vec![account::ADMIN], 2, 10, Sender(account::ADMIN));
+}
+
+#[test]
+fn test_deploy_saves_current_version() {
+    let voting = VotingContract::deployed(START_AT, END_AT);
+    assert_eq!(voting.version(), VERSION);
+}
+
 fn example_project(name: &str) -> Project {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(
+        "video".to_string(),
+        "https://www.youtube.com/channel/UCjFz9Sfi4yFwocnDQTWDSqA".to_string(),
+    );
+    attributes.insert(
+        "github".to_string(),
+        "https://github.com/CasperLabs/CasperLabs".to_string(),
+    );
+    attributes.insert(
+        "google_drive".to_string(),
+        "http://drive.google.com".to_string(),
+    );
     Project {
         name: name.to_string(),
         team_name: "casperlabs".to_string(),
-        video_link: "https://www.youtube.com/channel/UCjFz9Sfi4yFwocnDQTWDSqA".to_string(),
-        github_link: "https://github.com/CasperLabs/CasperLabs".to_string(),
-        google_drive_link: "http://drive.google.com".to_string(),
+        attributes,
     }
 }
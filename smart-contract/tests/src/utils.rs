@@ -56,10 +56,20 @@ impl SmartContractContext {
     }
 
     pub fn query_contract<T: CLTyped + FromBytes>(&self, name: &str) -> Option<T> {
-        match self
-            .context
-            .query(account::ADMIN, &[&self.contract_name, &name.to_string()])
-        {
+        self.query(account::ADMIN, &[&self.contract_name, name])
+    }
+
+    pub fn query_account<T: CLTyped + FromBytes>(
+        &self,
+        account: PublicKey,
+        name: &str,
+    ) -> Option<T> {
+        self.query(account, &[name])
+    }
+
+    fn query<T: CLTyped + FromBytes>(&self, account: PublicKey, path: &[&str]) -> Option<T> {
+        let name = path.last().unwrap();
+        match self.context.query(account, path) {
             Err(_) => None,
             Ok(maybe_value) => {
                 let value = maybe_value
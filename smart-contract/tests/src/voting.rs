@@ -1,24 +1,158 @@
 use crate::utils::SmartContractContext;
 pub use crate::utils::{account, Sender};
 use casperlabs_types::account::PublicKey;
-use logic::{Participant, Project, ProjectId, Voting};
+use logic::{
+    vote_commitment, Participant, ParticipantSerialized, Project, ProjectId, ProjectSerialized,
+    ResultsSerialized, Salt, Voting,
+};
+use std::collections::BTreeMap;
+
+pub mod vote_mode {
+    pub const ACCUMULATE: &str = "accumulate";
+    pub const REPLACE: &str = "replace";
+    pub const QUADRATIC: &str = "quadratic";
+    pub const RANKED: &str = "ranked";
+    pub const APPROVAL: &str = "approval";
+}
+
+pub mod role {
+    pub const OWNER: u8 = 1;
+    pub const PROJECT_MANAGER: u8 = 2;
+    pub const PARTICIPANT_MANAGER: u8 = 4;
+
+    pub const PROJECT_MANAGER_NAME: &str = "project_manager";
+    pub const PARTICIPANT_MANAGER_NAME: &str = "participant_manager";
+}
 
 mod method {
     pub const DEPLOY: &str = "deploy";
     pub const ADD_OR_UPDATE_PARTICIPANT: &str = "add_or_update_participant";
+    pub const ADD_OR_UPDATE_PARTICIPANTS: &str = "add_or_update_participants";
     pub const REMOVE_PARTICIPANT: &str = "remove_participant";
+    pub const ADD_TEAM_MEMBER: &str = "add_team_member";
+    pub const REMOVE_TEAM_MEMBER: &str = "remove_team_member";
     pub const ADD_OR_UPDATE_PROJECT: &str = "add_or_update_project";
+    pub const ADD_OR_UPDATE_PROJECTS: &str = "add_or_update_projects";
     pub const REMOVE_PROJECT: &str = "remove_project";
+    pub const SET_PROJECT_ATTRIBUTE: &str = "set_project_attribute";
+    pub const DELETE_PROJECT_ATTRIBUTE: &str = "delete_project_attribute";
     pub const CAST_VOTE: &str = "cast_vote";
+    pub const RETRACT_VOTE: &str = "retract_vote";
+    pub const CHANGE_VOTE: &str = "change_vote";
+    pub const CAST_RANKED_VOTE: &str = "cast_ranked_vote";
+    pub const COMMIT_VOTE: &str = "commit_vote";
+    pub const REVEAL_VOTE: &str = "reveal_vote";
+    pub const DELEGATE_VOTING_POWER: &str = "delegate_voting_power";
+    pub const REVOKE_DELEGATION: &str = "revoke_delegation";
+    pub const PUBLISH_RESULTS: &str = "publish_results";
+    pub const FINALIZE: &str = "finalize";
+    pub const SET_VOTING_WINDOW: &str = "set_voting_window";
+    pub const PAUSE: &str = "pause";
+    pub const UNPAUSE: &str = "unpause";
+    pub const PROPOSE_ADMIN: &str = "propose_admin";
+    pub const ACCEPT_ADMIN: &str = "accept_admin";
+    pub const GRANT_ROLE: &str = "grant_role";
+    pub const REVOKE_ROLE: &str = "revoke_role";
+    pub const SET_MULTISIG: &str = "set_multisig";
+    pub const GET_PROJECT: &str = "get_project";
+    pub const GET_PARTICIPANT: &str = "get_participant";
+    pub const GET_REMAINING_POWER: &str = "get_remaining_power";
+    pub const GET_RESULTS: &str = "get_results";
+}
+
+mod arg {
+    pub const START_AT: &str = "start_at";
+    pub const END_AT: &str = "end_at";
+    pub const VOTE_MODE: &str = "vote_mode";
+    pub const APPROVAL_LIMIT: &str = "approval_limit";
+    pub const REVEAL_END_AT: &str = "reveal_end_at";
+    pub const PARTICIPANT: &str = "participant";
+    pub const PARTICIPANTS: &str = "participants";
+    pub const VOTING_POWER: &str = "voting_power";
+    pub const PROJECT_ID: &str = "project_id";
+    pub const PROJECTS: &str = "projects";
+    pub const NAME: &str = "name";
+    pub const TEAM: &str = "team";
+    pub const ATTRIBUTES: &str = "attributes";
+    pub const KEY: &str = "key";
+    pub const VALUE: &str = "value";
+    pub const FROM_PROJECT_ID: &str = "from_project_id";
+    pub const TO_PROJECT_ID: &str = "to_project_id";
+    pub const RANKING: &str = "ranking";
+    pub const COMMITMENT: &str = "commitment";
+    pub const SALT: &str = "salt";
+    pub const DELEGATE: &str = "delegate";
+    pub const ADMIN: &str = "admin";
+    pub const ACCOUNT: &str = "account";
+    pub const ROLE: &str = "role";
+    pub const APPROVERS: &str = "approvers";
+    pub const THRESHOLD: &str = "threshold";
+    pub const TIMEOUT: &str = "timeout";
+}
+
+// Names of the arguments that follow, in the same order.
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
 }
 
-const VOTING_KEY: &str = "voting_data";
+const VERSION_KEY: &str = "voting_version";
+const CONFIG_KEY: &str = "voting_config";
+const PROJECTS_KEY: &str = "voting_projects";
+const PARTICIPANTS_KEY: &str = "voting_participants";
+const RESULTS_KEY: &str = "voting_results";
+const PAUSE_KEY: &str = "is_paused";
+const ROLES_KEY: &str = "roles";
+const PROJECT_ANSWER_KEY: &str = "query_project";
+const PARTICIPANT_ANSWER_KEY: &str = "query_participant";
+const REMAINING_POWER_ANSWER_KEY: &str = "query_remaining_power";
+const RESULTS_ANSWER_KEY: &str = "query_results";
 
 pub struct VotingContract(SmartContractContext);
 
 impl VotingContract {
     pub fn deployed(start_at: u64, end_at: u64) -> Self {
-        let init_args = (method::DEPLOY, start_at, end_at);
+        Self::deployed_with_vote_mode(start_at, end_at, vote_mode::ACCUMULATE)
+    }
+
+    pub fn deployed_with_vote_mode(start_at: u64, end_at: u64, vote_mode: &str) -> Self {
+        Self::deployed_with_approval_limit(start_at, end_at, vote_mode, 0)
+    }
+
+    pub fn deployed_with_approval_limit(
+        start_at: u64,
+        end_at: u64,
+        vote_mode: &str,
+        approval_limit: u64,
+    ) -> Self {
+        Self::deploy(start_at, end_at, vote_mode, approval_limit, 0)
+    }
+
+    pub fn deployed_with_secret_ballot(start_at: u64, end_at: u64, reveal_end_at: u64) -> Self {
+        Self::deploy(start_at, end_at, vote_mode::ACCUMULATE, 0, reveal_end_at)
+    }
+
+    fn deploy(
+        start_at: u64,
+        end_at: u64,
+        vote_mode: &str,
+        approval_limit: u64,
+        reveal_end_at: u64,
+    ) -> Self {
+        let init_args = (
+            method::DEPLOY,
+            names(&[
+                arg::START_AT,
+                arg::END_AT,
+                arg::VOTE_MODE,
+                arg::APPROVAL_LIMIT,
+                arg::REVEAL_END_AT,
+            ]),
+            start_at,
+            end_at,
+            vote_mode,
+            approval_limit,
+            reveal_end_at,
+        );
         let context =
             SmartContractContext::deployed("voting_indirect", "voting_contract", init_args);
         VotingContract(context)
@@ -29,8 +163,30 @@ impl VotingContract {
     }
 
     fn data(&self) -> Voting {
-        let serialized = self.0.query_contract(VOTING_KEY).unwrap();
-        Voting::deserialize(serialized)
+        let version = self.version();
+        let config = self.0.query_contract(CONFIG_KEY).unwrap();
+        let project_ids: Vec<u64> = self.0.query_contract(PROJECTS_KEY).unwrap_or_default();
+        let projects = project_ids
+            .into_iter()
+            .map(|project_id| {
+                let name = format!("project_{}", project_id);
+                (project_id, self.0.query_contract(&name).unwrap())
+            })
+            .collect();
+        let public_keys: Vec<[u8; 32]> =
+            self.0.query_contract(PARTICIPANTS_KEY).unwrap_or_default();
+        let participants = public_keys
+            .into_iter()
+            .map(|public_key| {
+                let hex: String = public_key
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+                let name = format!("participant_{}", hex);
+                (public_key, self.0.query_contract(&name).unwrap())
+            })
+            .collect();
+        Voting::deserialize((version, config, projects, participants)).unwrap()
     }
 
     pub fn start_at(&self) -> u64 {
@@ -41,6 +197,23 @@ impl VotingContract {
         self.data().end_at()
     }
 
+    pub fn is_finalized(&self) -> bool {
+        self.data().is_finalized()
+    }
+
+    pub fn version(&self) -> u8 {
+        self.0.query_contract(VERSION_KEY).unwrap()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.0.query_contract(PAUSE_KEY).unwrap()
+    }
+
+    pub fn has_role(&self, public_key: PublicKey, role: u8) -> bool {
+        let roles: BTreeMap<PublicKey, u8> = self.0.query_contract(ROLES_KEY).unwrap();
+        roles.get(&public_key).cloned().unwrap_or(0) & role != 0
+    }
+
     pub fn participant(&self, public_key: PublicKey) -> Option<Participant> {
         self.data().participants.get(&public_key).cloned()
     }
@@ -49,6 +222,11 @@ impl VotingContract {
         self.data().projects.get(&project_id).cloned()
     }
 
+    pub fn results(&self) -> Option<Vec<(ProjectId, u64)>> {
+        let serialized: Option<ResultsSerialized> = self.0.query_contract(RESULTS_KEY);
+        serialized.map(Voting::deserialize_results)
+    }
+
     pub fn add_or_update_participant(
         &mut self,
         public_key: PublicKey,
@@ -59,17 +237,62 @@ impl VotingContract {
             sender,
             (
                 (method::ADD_OR_UPDATE_PARTICIPANT, self.0.contract_hash),
+                names(&[arg::PARTICIPANT, arg::VOTING_POWER]),
                 public_key,
                 voting_power,
             ),
         );
     }
 
+    pub fn add_or_update_participants(
+        &mut self,
+        participants: Vec<(PublicKey, u64)>,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::ADD_OR_UPDATE_PARTICIPANTS, self.0.contract_hash),
+                names(&[arg::PARTICIPANTS]),
+                participants,
+            ),
+        );
+    }
+
     pub fn remove_participant(&mut self, public_key: PublicKey, sender: Sender) {
         self.0.call_indirect(
             sender,
             (
                 (method::REMOVE_PARTICIPANT, self.0.contract_hash),
+                names(&[arg::PARTICIPANT]),
+                public_key,
+            ),
+        );
+    }
+
+    pub fn add_team_member(
+        &mut self,
+        public_key: PublicKey,
+        project_id: ProjectId,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::ADD_TEAM_MEMBER, self.0.contract_hash),
+                names(&[arg::PARTICIPANT, arg::PROJECT_ID]),
+                public_key,
+                project_id.0,
+            ),
+        );
+    }
+
+    pub fn remove_team_member(&mut self, public_key: PublicKey, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::REMOVE_TEAM_MEMBER, self.0.contract_hash),
+                names(&[arg::PARTICIPANT]),
                 public_key,
             ),
         );
@@ -85,12 +308,46 @@ impl VotingContract {
             sender,
             (
                 (method::ADD_OR_UPDATE_PROJECT, self.0.contract_hash),
+                names(&[arg::PROJECT_ID, arg::NAME, arg::TEAM, arg::ATTRIBUTES]),
                 project_id.0,
                 project.name,
                 project.team_name,
-                project.video_link,
-                project.github_link,
-                project.google_drive_link,
+                project.attributes,
+            ),
+        );
+    }
+
+    // Sends the arguments in a different order and skips the optional attributes.
+    pub fn add_project_without_attributes(
+        &mut self,
+        project_id: ProjectId,
+        name: &str,
+        team_name: &str,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::ADD_OR_UPDATE_PROJECT, self.0.contract_hash),
+                names(&[arg::TEAM, arg::NAME, arg::PROJECT_ID]),
+                team_name,
+                name,
+                project_id.0,
+            ),
+        );
+    }
+
+    pub fn add_or_update_projects(
+        &mut self,
+        projects: BTreeMap<ProjectId, Project>,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::ADD_OR_UPDATE_PROJECTS, self.0.contract_hash),
+                names(&[arg::PROJECTS]),
+                Voting::serialize_projects(&projects),
             ),
         );
     }
@@ -98,7 +355,42 @@ impl VotingContract {
     pub fn remove_project(&mut self, project_id: ProjectId, sender: Sender) {
         self.0.call_indirect(
             sender,
-            ((method::REMOVE_PROJECT, self.0.contract_hash), project_id.0),
+            (
+                (method::REMOVE_PROJECT, self.0.contract_hash),
+                names(&[arg::PROJECT_ID]),
+                project_id.0,
+            ),
+        );
+    }
+
+    pub fn set_project_attribute(
+        &mut self,
+        project_id: ProjectId,
+        key: &str,
+        value: &str,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::SET_PROJECT_ATTRIBUTE, self.0.contract_hash),
+                names(&[arg::PROJECT_ID, arg::KEY, arg::VALUE]),
+                project_id.0,
+                key,
+                value,
+            ),
+        );
+    }
+
+    pub fn delete_project_attribute(&mut self, project_id: ProjectId, key: &str, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::DELETE_PROJECT_ATTRIBUTE, self.0.contract_hash),
+                names(&[arg::PROJECT_ID, arg::KEY]),
+                project_id.0,
+                key,
+            ),
         );
     }
 
@@ -107,9 +399,261 @@ impl VotingContract {
             sender,
             (
                 (method::CAST_VOTE, self.0.contract_hash),
+                names(&[arg::PROJECT_ID, arg::VOTING_POWER]),
                 project_id.0,
                 voting_power,
             ),
         );
     }
+
+    pub fn retract_vote(&mut self, project_id: ProjectId, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::RETRACT_VOTE, self.0.contract_hash),
+                names(&[arg::PROJECT_ID]),
+                project_id.0,
+            ),
+        );
+    }
+
+    pub fn change_vote(
+        &mut self,
+        from_project_id: ProjectId,
+        to_project_id: ProjectId,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::CHANGE_VOTE, self.0.contract_hash),
+                names(&[arg::FROM_PROJECT_ID, arg::TO_PROJECT_ID]),
+                from_project_id.0,
+                to_project_id.0,
+            ),
+        );
+    }
+
+    pub fn cast_ranked_vote(&mut self, ranking: Vec<ProjectId>, sender: Sender) {
+        let ranking: Vec<u64> = ranking.iter().map(|project_id| project_id.0).collect();
+        self.0.call_indirect(
+            sender,
+            (
+                (method::CAST_RANKED_VOTE, self.0.contract_hash),
+                names(&[arg::RANKING]),
+                ranking,
+            ),
+        );
+    }
+
+    pub fn commit_vote(
+        &mut self,
+        project_id: ProjectId,
+        voting_power: u64,
+        salt: Salt,
+        sender: Sender,
+    ) {
+        let commitment = vote_commitment(project_id, voting_power, salt);
+        self.0.call_indirect(
+            sender,
+            (
+                (method::COMMIT_VOTE, self.0.contract_hash),
+                names(&[arg::COMMITMENT]),
+                commitment,
+            ),
+        );
+    }
+
+    pub fn reveal_vote(
+        &mut self,
+        project_id: ProjectId,
+        voting_power: u64,
+        salt: Salt,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::REVEAL_VOTE, self.0.contract_hash),
+                names(&[arg::PROJECT_ID, arg::VOTING_POWER, arg::SALT]),
+                project_id.0,
+                voting_power,
+                salt,
+            ),
+        );
+    }
+
+    pub fn delegate_voting_power(
+        &mut self,
+        delegate: PublicKey,
+        voting_power: u64,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::DELEGATE_VOTING_POWER, self.0.contract_hash),
+                names(&[arg::DELEGATE, arg::VOTING_POWER]),
+                delegate,
+                voting_power,
+            ),
+        );
+    }
+
+    pub fn revoke_delegation(&mut self, delegate: PublicKey, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::REVOKE_DELEGATION, self.0.contract_hash),
+                names(&[arg::DELEGATE]),
+                delegate,
+            ),
+        );
+    }
+
+    pub fn publish_results(&mut self, sender: Sender) {
+        self.0
+            .call_indirect(sender, ((method::PUBLISH_RESULTS, self.0.contract_hash),));
+    }
+
+    pub fn finalize(&mut self, sender: Sender) {
+        self.0
+            .call_indirect(sender, ((method::FINALIZE, self.0.contract_hash),));
+    }
+
+    pub fn set_voting_window(&mut self, start_at: u64, end_at: u64, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::SET_VOTING_WINDOW, self.0.contract_hash),
+                names(&[arg::START_AT, arg::END_AT]),
+                start_at,
+                end_at,
+            ),
+        );
+    }
+
+    pub fn pause(&mut self, sender: Sender) {
+        self.0
+            .call_indirect(sender, ((method::PAUSE, self.0.contract_hash),));
+    }
+
+    pub fn unpause(&mut self, sender: Sender) {
+        self.0
+            .call_indirect(sender, ((method::UNPAUSE, self.0.contract_hash),));
+    }
+
+    pub fn propose_admin(&mut self, public_key: PublicKey, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::PROPOSE_ADMIN, self.0.contract_hash),
+                names(&[arg::ADMIN]),
+                public_key,
+            ),
+        );
+    }
+
+    pub fn accept_admin(&mut self, sender: Sender) {
+        self.0
+            .call_indirect(sender, ((method::ACCEPT_ADMIN, self.0.contract_hash),));
+    }
+
+    pub fn grant_role(&mut self, public_key: PublicKey, role: &str, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::GRANT_ROLE, self.0.contract_hash),
+                names(&[arg::ACCOUNT, arg::ROLE]),
+                public_key,
+                role,
+            ),
+        );
+    }
+
+    pub fn revoke_role(&mut self, public_key: PublicKey, role: &str, sender: Sender) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::REVOKE_ROLE, self.0.contract_hash),
+                names(&[arg::ACCOUNT, arg::ROLE]),
+                public_key,
+                role,
+            ),
+        );
+    }
+
+    pub fn set_multisig(
+        &mut self,
+        approvers: Vec<PublicKey>,
+        threshold: u64,
+        timeout: u64,
+        sender: Sender,
+    ) {
+        self.0.call_indirect(
+            sender,
+            (
+                (method::SET_MULTISIG, self.0.contract_hash),
+                names(&[arg::APPROVERS, arg::THRESHOLD, arg::TIMEOUT]),
+                approvers,
+                threshold,
+                timeout,
+            ),
+        );
+    }
+
+    // Queries save their answers in the sender's account.
+    pub fn get_project(&mut self, project_id: ProjectId, sender: Sender) -> Project {
+        let Sender(account) = sender;
+        self.0.call_indirect(
+            sender,
+            (
+                (method::GET_PROJECT, self.0.contract_hash),
+                names(&[arg::PROJECT_ID]),
+                project_id.0,
+            ),
+        );
+        let answer: ProjectSerialized = self.0.query_account(account, PROJECT_ANSWER_KEY).unwrap();
+        Voting::deserialize_project(answer)
+    }
+
+    pub fn get_participant(&mut self, public_key: PublicKey, sender: Sender) -> Participant {
+        let Sender(account) = sender;
+        self.0.call_indirect(
+            sender,
+            (
+                (method::GET_PARTICIPANT, self.0.contract_hash),
+                names(&[arg::PARTICIPANT]),
+                public_key,
+            ),
+        );
+        let answer: ParticipantSerialized = self
+            .0
+            .query_account(account, PARTICIPANT_ANSWER_KEY)
+            .unwrap();
+        Voting::deserialize_participant(answer)
+    }
+
+    pub fn get_remaining_power(&mut self, public_key: PublicKey, sender: Sender) -> u64 {
+        let Sender(account) = sender;
+        self.0.call_indirect(
+            sender,
+            (
+                (method::GET_REMAINING_POWER, self.0.contract_hash),
+                names(&[arg::PARTICIPANT]),
+                public_key,
+            ),
+        );
+        self.0
+            .query_account(account, REMAINING_POWER_ANSWER_KEY)
+            .unwrap()
+    }
+
+    pub fn get_results(&mut self, sender: Sender) -> Vec<(ProjectId, u64)> {
+        let Sender(account) = sender;
+        self.0
+            .call_indirect(sender, ((method::GET_RESULTS, self.0.contract_hash),));
+        let answer: ResultsSerialized = self.0.query_account(account, RESULTS_ANSWER_KEY).unwrap();
+        Voting::deserialize_results(answer)
+    }
 }